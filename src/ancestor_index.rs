@@ -0,0 +1,342 @@
+use snowflake::ProcessUniqueId;
+
+use iterators::PreOrderTraversalIds;
+use AncestorIndexError;
+use NodeId;
+use Tree;
+
+///
+/// An index over the ancestry of all `Node`s of a `Tree` that answers ancestor queries without
+/// walking up the `Tree`.
+///
+/// Building the index takes O(n log h) time and space for a `Tree` with n `Node`s and a height of
+/// h.  Afterwards `depth` and `is_ancestor` take constant time, while `lca` and `kth_ancestor`
+/// take O(log h) time.  Orphaned `Node`s are indexed as well.
+///
+/// The index doesn't borrow the `Tree`, so every query takes the `Tree` again.  Any structural
+/// change to the `Tree` (including `undo` and `redo`) outdates the index, after which all queries
+/// return `AncestorIndexError::OutdatedIndex` until it is built again.  Changing the data of a
+/// `Node` doesn't outdate it.
+///
+/// ```
+/// use id_tree::*;
+/// use id_tree::InsertBehavior::*;
+///
+/// let mut tree: Tree<i32> = Tree::new();
+/// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+/// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+/// let node_2 = tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+/// let node_3 = tree.insert(Node::new(3), UnderNode(&node_1)).unwrap();
+///
+/// let index = AncestorIndex::build(&tree);
+/// assert_eq!(index.depth(&tree, &node_2), Ok(2));
+/// assert_eq!(index.lca(&tree, &node_2, &node_3), Ok(Some(node_1.clone())));
+/// assert_eq!(index.kth_ancestor(&tree, &node_3, 2), Ok(Some(root_id.clone())));
+///
+/// tree.insert(Node::new(4), UnderNode(&root_id)).unwrap();
+/// assert!(index.is_outdated(&tree));
+/// assert_eq!(index.depth(&tree, &node_2), Err(AncestorIndexError::OutdatedIndex));
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct AncestorIndex {
+    revision: ProcessUniqueId,
+    // everything below is indexed by slot
+    depths: Vec<usize>,
+    // the position of each `Node` in a pre-order traversal of all subtrees, and the position of
+    // its last descendant
+    enter: Vec<usize>,
+    exit: Vec<usize>,
+    // `jumps[k][i]` is the slot 2^k levels above slot `i`, or the top-most ancestor of slot `i`
+    // if there aren't that many levels above it
+    jumps: Vec<Vec<usize>>,
+}
+
+impl AncestorIndex {
+    ///
+    /// Builds an `AncestorIndex` for the current structure of the given `Tree`.
+    ///
+    pub fn build<T>(tree: &Tree<T>) -> AncestorIndex {
+        let slots = tree.nodes.len();
+        let mut depths = vec![0usize; slots];
+        let mut enter = vec![0; slots];
+        let mut exit = vec![0; slots];
+        let mut parents: Vec<usize> = (0..slots).collect();
+
+        let mut order = Vec::with_capacity(tree.len());
+        for root_id in tree.forest_roots() {
+            for node_id in PreOrderTraversalIds::new(tree, root_id) {
+                if let Some(parent_id) = tree.get_unsafe(&node_id).parent() {
+                    parents[node_id.index] = parent_id.index;
+                    depths[node_id.index] = depths[parent_id.index] + 1;
+                }
+                enter[node_id.index] = order.len();
+                order.push(node_id);
+            }
+        }
+
+        // the last child of a `Node` comes after all of its other descendants
+        for node_id in order.iter().rev() {
+            exit[node_id.index] = match tree.get_unsafe(node_id).children().last() {
+                Some(last_id) => exit[last_id.index],
+                None => enter[node_id.index],
+            };
+        }
+
+        let max_depth = depths.iter().cloned().max().unwrap_or(0);
+        let levels = std::cmp::max(1, (usize::BITS - max_depth.leading_zeros()) as usize);
+        let mut jumps = Vec::with_capacity(levels);
+        jumps.push(parents);
+        for level in 1..levels {
+            let below: &Vec<usize> = &jumps[level - 1];
+            let next = below.iter().map(|&slot| below[slot]).collect();
+            jumps.push(next);
+        }
+
+        AncestorIndex {
+            revision: tree.revision,
+            depths,
+            enter,
+            exit,
+            jumps,
+        }
+    }
+
+    ///
+    /// Returns `true` if the given `Tree` isn't the one this index was built from, or if its
+    /// structure has changed since.
+    ///
+    pub fn is_outdated<T>(&self, tree: &Tree<T>) -> bool {
+        self.revision != tree.revision
+    }
+
+    ///
+    /// Returns the number of ancestors of a `Node`, just like `Tree::depth` does.
+    ///
+    /// Returns a `Result` containing the depth or an `AncestorIndexError` if one occurred.
+    ///
+    pub fn depth<T>(&self, tree: &Tree<T>, node_id: &NodeId) -> Result<usize, AncestorIndexError> {
+        self.check(tree, node_id)?;
+        Ok(self.depths[node_id.index])
+    }
+
+    ///
+    /// Returns `true` if the `Node` identified by `ancestor_id` is an ancestor of the `Node`
+    /// identified by `node_id`, just like `Tree::is_ancestor_of` does.
+    ///
+    /// Returns a `Result` containing the answer or an `AncestorIndexError` if one occurred.
+    ///
+    pub fn is_ancestor<T>(
+        &self,
+        tree: &Tree<T>,
+        ancestor_id: &NodeId,
+        node_id: &NodeId,
+    ) -> Result<bool, AncestorIndexError> {
+        self.check(tree, ancestor_id)?;
+        self.check(tree, node_id)?;
+        Ok(ancestor_id.index != node_id.index && self.contains(ancestor_id.index, node_id.index))
+    }
+
+    ///
+    /// Returns the `NodeId` of the lowest common ancestor of two `Node`s, just like
+    /// `Tree::lowest_common_ancestor` does.
+    ///
+    /// Returns a `Result` containing the `NodeId` or an `AncestorIndexError` if one occurred.
+    ///
+    pub fn lca<T>(
+        &self,
+        tree: &Tree<T>,
+        a: &NodeId,
+        b: &NodeId,
+    ) -> Result<Option<NodeId>, AncestorIndexError> {
+        self.check(tree, a)?;
+        self.check(tree, b)?;
+
+        if self.contains(a.index, b.index) {
+            return Ok(Some(a.clone()));
+        }
+        if self.contains(b.index, a.index) {
+            return Ok(Some(b.clone()));
+        }
+
+        // go up from `a` as far as possible without reaching an ancestor of `b`
+        let mut slot = a.index;
+        for jumps in self.jumps.iter().rev() {
+            if !self.contains(jumps[slot], b.index) {
+                slot = jumps[slot];
+            }
+        }
+        let lca = self.jumps[0][slot];
+
+        // `a` and `b` are in different subtrees if even the top-most ancestor of `a` isn't one
+        if self.contains(lca, b.index) {
+            Ok(Some(tree.new_node_id(lca)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    ///
+    /// Returns the `NodeId` of the ancestor `k` levels above a `Node`: the `Node` itself for a
+    /// `k` of 0, its parent for a `k` of 1 and so on.
+    ///
+    /// Returns `None` if `k` is larger than the depth of the `Node`.
+    ///
+    /// Returns a `Result` containing the `NodeId` or an `AncestorIndexError` if one occurred.
+    ///
+    pub fn kth_ancestor<T>(
+        &self,
+        tree: &Tree<T>,
+        node_id: &NodeId,
+        k: usize,
+    ) -> Result<Option<NodeId>, AncestorIndexError> {
+        self.check(tree, node_id)?;
+
+        if k > self.depths[node_id.index] {
+            return Ok(None);
+        }
+
+        let mut slot = node_id.index;
+        for (level, jumps) in self.jumps.iter().enumerate() {
+            if k & (1 << level) != 0 {
+                slot = jumps[slot];
+            }
+        }
+
+        Ok(Some(tree.new_node_id(slot)))
+    }
+
+    // Returns `true` if the `Node` in slot `ancestor` is the `Node` in slot `node` or one of its
+    // ancestors.
+    fn contains(&self, ancestor: usize, node: usize) -> bool {
+        self.enter[ancestor] <= self.enter[node] && self.exit[node] <= self.exit[ancestor]
+    }
+
+    fn check<T>(&self, tree: &Tree<T>, node_id: &NodeId) -> Result<(), AncestorIndexError> {
+        if self.is_outdated(tree) {
+            return Err(AncestorIndexError::OutdatedIndex);
+        }
+        tree.get(node_id)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::AncestorIndex;
+    use AncestorIndexError;
+    use InsertBehavior::*;
+    use Node;
+    use NodeId;
+    use NodeIdError;
+    use RemoveBehavior::*;
+    use Tree;
+
+    // a `Tree` of `count` `Node`s with pseudo-random parents
+    fn random_tree(count: usize) -> (Tree<usize>, Vec<NodeId>) {
+        let mut tree = Tree::new();
+        let mut ids = vec![tree.insert(Node::new(0), AsRoot).unwrap()];
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        for i in 1..count {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            // prefer recent `Node`s as parents to get some deeper chains
+            let parent = i - 1 - (state as usize % std::cmp::min(i, 8 + i / 4));
+            let id = tree.insert(Node::new(i), UnderNode(&ids[parent])).unwrap();
+            ids.push(id);
+        }
+        (tree, ids)
+    }
+
+    #[test]
+    fn test_matches_tree() {
+        let (tree, ids) = random_tree(300);
+        let index = AncestorIndex::build(&tree);
+
+        for a in ids.iter() {
+            let depth = tree.depth(a).unwrap();
+            assert_eq!(index.depth(&tree, a), Ok(depth));
+
+            let ancestors: Vec<NodeId> = tree.ancestor_ids(a).unwrap().cloned().collect();
+            assert_eq!(index.kth_ancestor(&tree, a, 0), Ok(Some(a.clone())));
+            for (k, ancestor_id) in ancestors.iter().enumerate() {
+                assert_eq!(
+                    index.kth_ancestor(&tree, a, k + 1),
+                    Ok(Some(ancestor_id.clone()))
+                );
+            }
+            assert_eq!(index.kth_ancestor(&tree, a, depth + 1), Ok(None));
+
+            for b in ids.iter().step_by(7) {
+                assert_eq!(
+                    index.lca(&tree, a, b),
+                    Ok(tree.lowest_common_ancestor(a, b).unwrap())
+                );
+                assert_eq!(
+                    index.is_ancestor(&tree, a, b),
+                    Ok(tree.is_ancestor_of(a, b).unwrap())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_orphans() {
+        let (mut tree, ids) = random_tree(100);
+        tree.remove_node(ids[1].clone(), OrphanChildren).unwrap();
+        tree.remove_node(ids[5].clone(), LiftChildren).unwrap();
+        let index = AncestorIndex::build(&tree);
+
+        for a in ids.iter().filter(|id| tree.get(id).is_ok()) {
+            assert_eq!(index.depth(&tree, a), Ok(tree.depth(a).unwrap()));
+            for b in ids.iter().filter(|id| tree.get(id).is_ok()) {
+                assert_eq!(
+                    index.lca(&tree, a, b),
+                    Ok(tree.lowest_common_ancestor(a, b).unwrap())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_outdated() {
+        let (mut tree, ids) = random_tree(10);
+        tree.enable_journal();
+        let index = AncestorIndex::build(&tree);
+        assert!(!index.is_outdated(&tree));
+
+        // changing data keeps the index valid
+        *tree.get_mut(&ids[3]).unwrap().data_mut() = 17;
+        tree.replace_data(&ids[4], 18).unwrap();
+        assert!(!index.is_outdated(&tree));
+
+        let removed_id = ids[9].clone();
+        tree.remove_node(removed_id.clone(), DropChildren).unwrap();
+        assert!(index.is_outdated(&tree));
+        assert_eq!(
+            index.depth(&tree, &ids[2]),
+            Err(AncestorIndexError::OutdatedIndex)
+        );
+
+        let index = AncestorIndex::build(&tree);
+        assert_eq!(
+            index.depth(&tree, &removed_id),
+            Err(AncestorIndexError::NodeIdError(
+                NodeIdError::NodeIdNoLongerValid
+            ))
+        );
+
+        assert!(tree.undo());
+        assert!(index.is_outdated(&tree));
+
+        let copy = tree.clone();
+        let index = AncestorIndex::build(&tree);
+        assert!(index.is_outdated(&copy));
+        assert_eq!(
+            index.lca(&copy, &ids[0], &ids[0]),
+            Err(AncestorIndexError::OutdatedIndex)
+        );
+    }
+}
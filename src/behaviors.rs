@@ -3,6 +3,7 @@ use NodeId;
 ///
 /// Describes the possible behaviors of the `Tree::remove_node` method.
 ///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RemoveBehavior {
     ///
     /// All children will be dropped recursively.  In other words, the entire sub-tree of the `Node`
@@ -149,6 +150,32 @@ pub enum MoveBehavior<'a> {
     /// ```
     ///
     ToParent(&'a NodeId),
+
+    ///
+    /// Detaches a `Node` from its parent, leaving all children in their place.  The `Node`
+    /// becomes the root `Node` if there is none yet.  Otherwise it is left without a parent next
+    /// to the root `Node` (see `Tree::orphan_ids`), which is how a `Forest` gets a new root.
+    ///
+    /// Moving a `Node` that already has no parent does nothing.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    /// use id_tree::MoveBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    ///
+    /// let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    ///
+    /// tree.move_node(&child_id, ToNewRoot).unwrap();
+    ///
+    /// assert_eq!(tree.root_node_id(), Some(&root_id));
+    /// assert_eq!(tree.get(&child_id).unwrap().parent(), None);
+    /// assert_eq!(tree.orphan_ids().collect::<Vec<_>>(), vec![child_id]);
+    /// ```
+    ///
+    ToNewRoot,
 }
 
 ///
@@ -190,11 +217,97 @@ pub enum InsertBehavior<'a> {
     /// tree.insert(child_node, UnderNode(&root_id)).unwrap();
     /// ```
     UnderNode(&'a NodeId),
+
+    ///
+    /// Adds the new `Node` as a child of the `Node` specified by the given `NodeId`, at the given
+    /// position amongst its children.  The children that were at or after that position are
+    /// shifted back by one.
+    ///
+    /// A position equal to the number of children adds the new `Node` to the end of the children
+    /// (just like `UnderNode` does).  Any larger position results in a
+    /// `NodeIdError::InvalidChildIndex`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let two_id = tree.insert(Node::new(2), UnderNodeAt(&root_id, 1)).unwrap();
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().children()[1], two_id);
+    /// assert!(tree.insert(Node::new(4), UnderNodeAt(&root_id, 4)).is_err());
+    /// ```
+    UnderNodeAt(&'a NodeId, usize),
+
+    ///
+    /// Adds the new `Node` as a sibling of the `Node` specified by the given `NodeId`, directly
+    /// before it in the children of their common parent.
+    ///
+    /// Returns a `NodeIdError::NodeHasNoParent` if the given `Node` doesn't have a parent (which
+    /// is the case for the root `Node`).
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let two_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    ///
+    /// let one_id = tree.insert(Node::new(1), BeforeSibling(&two_id)).unwrap();
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().children(), &vec![one_id, two_id]);
+    /// ```
+    BeforeSibling(&'a NodeId),
+
+    ///
+    /// Adds the new `Node` as a sibling of the `Node` specified by the given `NodeId`, directly
+    /// after it in the children of their common parent.
+    ///
+    /// Returns a `NodeIdError::NodeHasNoParent` if the given `Node` doesn't have a parent (which
+    /// is the case for the root `Node`).
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let one_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// let two_id = tree.insert(Node::new(2), AfterSibling(&one_id)).unwrap();
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().children(), &vec![one_id, two_id]);
+    /// ```
+    AfterSibling(&'a NodeId),
+
+    ///
+    /// Adds the new `Node` without a parent.  It becomes the root `Node` if there is none yet.
+    /// Otherwise it is left without a parent next to the root `Node` (see `Tree::orphan_ids`),
+    /// which is how a `Forest` gets a new root.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsNewRoot).unwrap();
+    /// let other_id = tree.insert(Node::new(1), AsNewRoot).unwrap();
+    ///
+    /// assert_eq!(tree.root_node_id(), Some(&root_id));
+    /// assert_eq!(tree.forest_roots().collect::<Vec<_>>(), vec![root_id, other_id]);
+    /// ```
+    AsNewRoot,
 }
 
 ///
 /// Describes the possible behaviors of the `Tree::swap_nodes` method.
 ///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SwapBehavior {
     ///
     /// Take the children of the `Node`s being swapped with them.  In other words, this swaps the
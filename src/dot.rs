@@ -0,0 +1,195 @@
+use std::fmt;
+use std::fmt::Display;
+
+///
+/// Controls how `Tree::write_dot` renders a `Tree` in the Graphviz DOT language.
+///
+/// ```
+/// use id_tree::*;
+/// use id_tree::InsertBehavior::*;
+///
+/// let mut tree: Tree<i32> = Tree::new();
+/// let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+/// tree.insert(Node::new(-2), UnderNode(&root_id)).unwrap();
+///
+/// let options = DotOptions::new()
+///     .with_node_ids(true)
+///     .with_attributes(|data: &i32| {
+///         if *data < 0 {
+///             vec![("color".to_string(), "red".to_string())]
+///         } else {
+///             vec![]
+///         }
+///     });
+///
+/// let mut s = String::new();
+/// tree.write_dot(&mut s, &options).unwrap();
+/// assert_eq!(&s, "\
+/// digraph {
+///     n0 [label=\"0: 1\"];
+///     n1 [label=\"1: -2\", color=\"red\"];
+///     n0 -> n1;
+/// }
+/// ");
+/// ```
+///
+pub struct DotOptions<'a, T> {
+    label: Box<dyn Fn(&T) -> String + 'a>,
+    attributes: Option<AttributesFn<'a, T>>,
+    node_ids: bool,
+    orphans: bool,
+}
+
+type AttributesFn<'a, T> = Box<dyn Fn(&T) -> Vec<(String, String)> + 'a>;
+
+impl<'a, T: Display> Default for DotOptions<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> DotOptions<'a, T> {
+    ///
+    /// Creates a new `DotOptions` that labels every `Node` with the `Display` representation of
+    /// its data.
+    ///
+    /// ```
+    /// use id_tree::DotOptions;
+    ///
+    /// let _options: DotOptions<i32> = DotOptions::new();
+    /// ```
+    ///
+    pub fn new() -> DotOptions<'a, T>
+    where
+        T: Display,
+    {
+        DotOptions::from_label_fn(|data: &T| data.to_string())
+    }
+
+    ///
+    /// Creates a new `DotOptions` that labels every `Node` with the `String` returned by the
+    /// given function.
+    ///
+    /// ```
+    /// use id_tree::DotOptions;
+    ///
+    /// let _options: DotOptions<Vec<i32>> =
+    ///     DotOptions::from_label_fn(|data: &Vec<i32>| format!("{} items", data.len()));
+    /// ```
+    ///
+    pub fn from_label_fn<F>(label: F) -> DotOptions<'a, T>
+    where
+        F: Fn(&T) -> String + 'a,
+    {
+        DotOptions {
+            label: Box::new(label),
+            attributes: None,
+            node_ids: false,
+            orphans: false,
+        }
+    }
+
+    ///
+    /// Sets whether the index of each `NodeId` is put in front of the label of its `Node`.
+    ///
+    /// Defaults to `false`.
+    ///
+    pub fn with_node_ids(mut self, node_ids: bool) -> DotOptions<'a, T> {
+        self.node_ids = node_ids;
+        self
+    }
+
+    ///
+    /// Sets whether `Node`s that cannot be reached from the root `Node` (and their descendants)
+    /// are written as well.
+    ///
+    /// Defaults to `false`.
+    ///
+    pub fn with_orphans(mut self, orphans: bool) -> DotOptions<'a, T> {
+        self.orphans = orphans;
+        self
+    }
+
+    ///
+    /// Sets a function that returns additional attributes (such as `color` or `shape`) for each
+    /// `Node`, as pairs of names and values.  The values are quoted and escaped when written, and
+    /// so are names that aren't plain DOT identifiers.
+    ///
+    pub fn with_attributes<F>(mut self, attributes: F) -> DotOptions<'a, T>
+    where
+        F: Fn(&T) -> Vec<(String, String)> + 'a,
+    {
+        self.attributes = Some(Box::new(attributes));
+        self
+    }
+
+    pub(crate) fn orphans(&self) -> bool {
+        self.orphans
+    }
+
+    pub(crate) fn write_node<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        index: usize,
+        data: &T,
+    ) -> fmt::Result {
+        let label = (self.label)(data);
+
+        write!(w, "    n{} [label=\"", index)?;
+        if self.node_ids {
+            write!(w, "{}: ", index)?;
+        }
+        write_escaped(w, &label)?;
+        write!(w, "\"")?;
+
+        if let Some(ref attributes) = self.attributes {
+            for (name, value) in attributes(data) {
+                write!(w, ", ")?;
+                write_id(w, &name)?;
+                write!(w, "=\"")?;
+                write_escaped(w, &value)?;
+                write!(w, "\"")?;
+            }
+        }
+
+        writeln!(w, "];")
+    }
+}
+
+impl<'a, T> fmt::Debug for DotOptions<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DotOptions")
+            .field("node_ids", &self.node_ids)
+            .field("orphans", &self.orphans)
+            .finish()
+    }
+}
+
+// Writes an identifier as it is if DOT accepts it unquoted, quoted and escaped otherwise.
+fn write_id<W: fmt::Write>(w: &mut W, s: &str) -> fmt::Result {
+    let plain = s
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if plain {
+        w.write_str(s)
+    } else {
+        w.write_char('"')?;
+        write_escaped(w, s)?;
+        w.write_char('"')
+    }
+}
+
+fn write_escaped<W: fmt::Write>(w: &mut W, s: &str) -> fmt::Result {
+    for c in s.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            '\n' => w.write_str("\\n")?,
+            _ => w.write_char(c)?,
+        }
+    }
+    Ok(())
+}
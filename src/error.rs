@@ -1,6 +1,9 @@
 use std::error::Error;
 use std::fmt;
 
+use NodeId;
+use Tree;
+
 ///
 /// Enum for all of the possible `NodeId` errors that could occur.
 ///
@@ -10,6 +13,11 @@ pub enum NodeIdError {
     InvalidNodeIdForTree,
     /// Occurs when a `NodeId` is used on a `Tree` after the corresponding `Node` has been removed.
     NodeIdNoLongerValid,
+    /// Occurs when a child position is larger than the number of children of the `Node` in
+    /// question.
+    InvalidChildIndex,
+    /// Occurs when an operation requires a `Node` to have a parent, but it doesn't have one.
+    NodeHasNoParent,
 }
 
 impl NodeIdError {
@@ -20,6 +28,11 @@ impl NodeIdError {
                 "The given NodeId is no longer valid. The Node in question has been \
                  removed."
             }
+            NodeIdError::InvalidChildIndex => {
+                "The given child index is out of bounds for the children of the Node in \
+                 question."
+            }
+            NodeIdError::NodeHasNoParent => "The Node in question doesn't have a parent.",
         }
     }
 }
@@ -35,3 +48,293 @@ impl Error for NodeIdError {
         self.to_string()
     }
 }
+
+///
+/// Enum for the errors that can occur while writing a `Tree` with a `TreeFormatter`.
+///
+#[derive(Debug, Eq, PartialEq)]
+pub enum FormatError {
+    /// Occurs when the `NodeId` to start from can't be used on the `Tree` being written.
+    NodeIdError(NodeIdError),
+    /// Occurs when the writer that is written to returns an error.
+    WriteError(fmt::Error),
+}
+
+impl From<NodeIdError> for FormatError {
+    fn from(error: NodeIdError) -> FormatError {
+        FormatError::NodeIdError(error)
+    }
+}
+
+impl From<fmt::Error> for FormatError {
+    fn from(error: fmt::Error) -> FormatError {
+        FormatError::WriteError(error)
+    }
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FormatError::NodeIdError(ref error) => write!(f, "FormatError: {}", error),
+            FormatError::WriteError(_) => write!(f, "FormatError: The writer returned an error."),
+        }
+    }
+}
+
+impl Error for FormatError {
+    fn description(&self) -> &str {
+        match *self {
+            FormatError::NodeIdError(ref error) => error.to_string(),
+            FormatError::WriteError(_) => "The writer returned an error.",
+        }
+    }
+}
+
+///
+/// Enum for the errors that can occur while querying an `AncestorIndex`.
+///
+#[derive(Debug, Eq, PartialEq)]
+pub enum AncestorIndexError {
+    /// Occurs when a `NodeId` can't be used on the `Tree` that is queried.
+    NodeIdError(NodeIdError),
+    /// Occurs when the `Tree` that is queried isn't the one the index was built from, or when its
+    /// structure has changed since.
+    OutdatedIndex,
+}
+
+impl From<NodeIdError> for AncestorIndexError {
+    fn from(error: NodeIdError) -> AncestorIndexError {
+        AncestorIndexError::NodeIdError(error)
+    }
+}
+
+impl fmt::Display for AncestorIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AncestorIndexError::NodeIdError(ref error) => {
+                write!(f, "AncestorIndexError: {}", error)
+            }
+            AncestorIndexError::OutdatedIndex => write!(
+                f,
+                "AncestorIndexError: The index doesn't match the current structure of the Tree."
+            ),
+        }
+    }
+}
+
+impl Error for AncestorIndexError {
+    fn description(&self) -> &str {
+        match *self {
+            AncestorIndexError::NodeIdError(ref error) => error.to_string(),
+            AncestorIndexError::OutdatedIndex => {
+                "The index doesn't match the current structure of the Tree."
+            }
+        }
+    }
+}
+
+///
+/// An error that occurred while grafting a `Tree` onto another one, along with the `Tree` that
+/// was to be grafted.  None of its `Node`s have been moved.
+///
+#[derive(Debug)]
+pub struct GraftError<T> {
+    error: NodeIdError,
+    tree: Box<Tree<T>>,
+}
+
+impl<T> GraftError<T> {
+    pub(crate) fn new(error: NodeIdError, tree: Tree<T>) -> GraftError<T> {
+        GraftError {
+            error,
+            tree: Box::new(tree),
+        }
+    }
+
+    ///
+    /// Returns what went wrong.
+    ///
+    pub fn error(&self) -> &NodeIdError {
+        &self.error
+    }
+
+    ///
+    /// Returns the `Tree` that was to be grafted, exactly as it was handed to `graft`.
+    ///
+    pub fn into_tree(self) -> Tree<T> {
+        *self.tree
+    }
+}
+
+impl<T> fmt::Display for GraftError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GraftError: {}", self.error)
+    }
+}
+
+impl<T: fmt::Debug> Error for GraftError<T> {
+    fn description(&self) -> &str {
+        self.error.to_string()
+    }
+}
+
+///
+/// An error that occurred while parsing a `Tree` from text, along with the (1-based) line and
+/// column it occurred at.
+///
+#[derive(Debug, Eq, PartialEq)]
+pub struct ParseError<E> {
+    line: usize,
+    column: usize,
+    kind: ParseErrorKind<E>,
+}
+
+impl<E> ParseError<E> {
+    pub(crate) fn new(line: usize, column: usize, kind: ParseErrorKind<E>) -> ParseError<E> {
+        ParseError { line, column, kind }
+    }
+
+    ///
+    /// Returns the line the error occurred on, starting at 1.
+    ///
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    ///
+    /// Returns the column (in characters) the error occurred at, starting at 1.
+    ///
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    ///
+    /// Returns what went wrong.
+    ///
+    pub fn kind(&self) -> &ParseErrorKind<E> {
+        &self.kind
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ParseError: line {}, column {}: ",
+            self.line, self.column
+        )?;
+        match self.kind {
+            ParseErrorKind::InvalidLabel(ref error) => write!(f, "Invalid label: {}", error),
+            ref kind => write!(f, "{}", kind.to_string()),
+        }
+    }
+}
+
+impl<E: Error> Error for ParseError<E> {
+    fn description(&self) -> &str {
+        self.kind.to_string()
+    }
+}
+
+///
+/// Enum for all of the things that can go wrong while parsing a `Tree` from text.
+///
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseErrorKind<E> {
+    /// Occurs when the function that parses labels returns an error.
+    InvalidLabel(E),
+    /// Occurs when the characters in front of a label don't describe a valid depth.
+    InvalidIndentation,
+    /// Occurs when a line is more than one level deeper than the line before it.
+    MissingParent,
+    /// Occurs when a second line has no indentation.
+    MultipleRoots,
+}
+
+impl<E> ParseErrorKind<E> {
+    fn to_string(&self) -> &str {
+        match *self {
+            ParseErrorKind::InvalidLabel(_) => "The label could not be parsed.",
+            ParseErrorKind::InvalidIndentation => "The indentation is not valid.",
+            ParseErrorKind::MissingParent => {
+                "The line is more than one level deeper than the line before it."
+            }
+            ParseErrorKind::MultipleRoots => "The Tree can't have more than one root Node.",
+        }
+    }
+}
+
+///
+/// Enum for all of the ways the internal structure of a `Tree` can be broken.
+///
+#[derive(Debug, Eq, PartialEq)]
+pub enum IntegrityError {
+    /// Occurs when there isn't exactly one generation for each slot.
+    GenerationsLength,
+    /// Occurs when a `NodeId` that belongs to a different `Tree` is found.
+    ForeignNodeId(NodeId),
+    /// Occurs when a `NodeId` points to an empty slot, to a slot that doesn't exist, or to a
+    /// slot that has been re-used since.
+    DanglingNodeId(NodeId),
+    /// Occurs when the root `Node` has a parent.
+    RootHasParent(NodeId),
+    /// Occurs when a `Node` is amongst the children of a `Node` that isn't its parent.
+    ParentMismatch { parent: NodeId, child: NodeId },
+    /// Occurs when a `Node` is not amongst the children of its parent exactly once.
+    ChildrenMismatch { parent: NodeId, child: NodeId },
+    /// Occurs when a `Node` is one of its own ancestors.
+    Cycle(NodeId),
+    /// Occurs when the free `NodeId`s don't match the empty slots exactly.  Holds the index of
+    /// the first slot found that doesn't match.
+    FreeIdsMismatch(usize),
+}
+
+impl fmt::Display for IntegrityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IntegrityError: ")?;
+        match *self {
+            IntegrityError::GenerationsLength => {
+                write!(f, "There isn't exactly one generation for each slot.")
+            }
+            IntegrityError::ForeignNodeId(ref node_id) => {
+                write!(f, "{:?} belongs to a different Tree.", node_id)
+            }
+            IntegrityError::DanglingNodeId(ref node_id) => {
+                write!(f, "{:?} doesn't point to a Node.", node_id)
+            }
+            IntegrityError::RootHasParent(ref node_id) => {
+                write!(f, "The root Node {:?} has a parent.", node_id)
+            }
+            IntegrityError::ParentMismatch {
+                ref parent,
+                ref child,
+            } => write!(
+                f,
+                "{:?} is a child of {:?}, but that isn't its parent.",
+                child, parent
+            ),
+            IntegrityError::ChildrenMismatch {
+                ref parent,
+                ref child,
+            } => write!(
+                f,
+                "{:?} is not amongst the children of its parent {:?} exactly once.",
+                child, parent
+            ),
+            IntegrityError::Cycle(ref node_id) => {
+                write!(f, "{:?} is one of its own ancestors.", node_id)
+            }
+            IntegrityError::FreeIdsMismatch(index) => write!(
+                f,
+                "The free NodeIds don't match the empty slots at index {}.",
+                index
+            ),
+        }
+    }
+}
+
+impl Error for IntegrityError {
+    fn description(&self) -> &str {
+        "The internal structure of the Tree is broken."
+    }
+}
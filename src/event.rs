@@ -0,0 +1,103 @@
+use NodeId;
+use RemoveBehavior;
+use SwapBehavior;
+
+///
+/// Describes a change that was made to a `Tree`.
+///
+/// `TreeEvent`s are only collected after they have been turned on with `Tree::enable_events` and
+/// can be taken out of the `Tree` with `Tree::drain_events`.  Each successful call to a method
+/// that changes the `Tree` adds its events in the order the changes were made.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TreeEvent {
+    ///
+    /// A `Node` was inserted.  `index` is its position amongst the children of `parent` (or `0`
+    /// if it was inserted as the root `Node`).
+    ///
+    /// When a subtree is inserted (for example by `Tree::graft`), there is one event for every
+    /// `Node` in pre-order, so the parent of each `Node` is always inserted before the `Node`.
+    ///
+    Inserted {
+        id: NodeId,
+        parent: Option<NodeId>,
+        index: usize,
+    },
+
+    ///
+    /// A `Node` was removed, along with all of its descendants if `behavior` is `DropChildren`.
+    /// `Tree::remove_subtree` reports `DropChildren` as well.
+    ///
+    /// With `LiftChildren` the children were appended to the children of the parent of the
+    /// removed `Node`, with `OrphanChildren` they were left without a parent.
+    ///
+    Removed {
+        id: NodeId,
+        behavior: RemoveBehavior,
+    },
+
+    ///
+    /// A `Node` was moved, along with all of its descendants.  It is now the last child of
+    /// `new_parent`, or has no parent if `new_parent` is `None`.
+    ///
+    Moved {
+        id: NodeId,
+        old_parent: Option<NodeId>,
+        new_parent: Option<NodeId>,
+    },
+
+    ///
+    /// Two `Node`s were swapped with `Tree::swap_nodes`.
+    ///
+    Swapped {
+        first: NodeId,
+        second: NodeId,
+        behavior: SwapBehavior,
+    },
+
+    ///
+    /// The children of a `Node` were put in a new order, for example by one of the
+    /// `sort_children_by*` methods.
+    ///
+    ChildrenReordered { parent: NodeId },
+
+    ///
+    /// The data of a `Node` was replaced with `Tree::replace_data`.
+    ///
+    DataReplaced { id: NodeId },
+
+    ///
+    /// A change was reverted with `Tree::undo` or re-applied with `Tree::redo`.  Any part of the
+    /// structure of the `Tree` (and the data of the `Node`s that were brought back) may have
+    /// changed, so anything that mirrors the `Tree` has to be rebuilt from it.
+    ///
+    Reset,
+}
+
+// Collects the events of a `Tree`.  Like the journal, only the outermost of several nested
+// mutations reports its changes.
+#[derive(Debug, Default)]
+pub(crate) struct EventQueue {
+    events: Vec<TreeEvent>,
+    depth: usize,
+}
+
+impl EventQueue {
+    pub(crate) fn begin(&mut self) {
+        self.depth += 1;
+    }
+
+    pub(crate) fn end(&mut self) {
+        self.depth -= 1;
+    }
+
+    pub(crate) fn push(&mut self, event: TreeEvent) {
+        if self.depth <= 1 {
+            self.events.push(event);
+        }
+    }
+
+    pub(crate) fn drain(&mut self) -> Vec<TreeEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
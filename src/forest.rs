@@ -0,0 +1,502 @@
+use iterators::{
+    LevelOrderTraversal, LevelOrderTraversalIds, PostOrderTraversal, PostOrderTraversalIds,
+    PreOrderTraversal, PreOrderTraversalIds,
+};
+use InsertBehavior;
+use MoveBehavior;
+use Node;
+use NodeId;
+use NodeIdError;
+use RemoveBehavior;
+use Tree;
+
+///
+/// A collection of trees that share their `Node`s and `NodeId`s, with an ordered list of roots.
+///
+/// A `Forest` is a `Tree` without a root `Node` in which every `Node` without a parent is one of
+/// the roots.  All `NodeId`s are checked just like they are by a `Tree`.
+///
+/// ```
+/// use id_tree::*;
+/// use id_tree::InsertBehavior::*;
+/// use id_tree::MoveBehavior::*;
+///
+/// let mut forest: Forest<i32> = Forest::new();
+/// let first_id = forest.insert(Node::new(0), AsNewRoot).unwrap();
+/// let second_id = forest.insert(Node::new(1), AsNewRoot).unwrap();
+/// let child_id = forest.insert(Node::new(2), UnderNode(&first_id)).unwrap();
+///
+/// forest.move_node(&child_id, ToNewRoot).unwrap();
+/// assert_eq!(forest.roots(), &[first_id, second_id, child_id]);
+///
+/// let data: Vec<i32> = forest.traverse_pre_order().map(|node| *node.data()).collect();
+/// assert_eq!(data, vec![0, 1, 2]);
+/// ```
+///
+#[derive(Debug)]
+pub struct Forest<T> {
+    tree: Tree<T>,
+    roots: Vec<NodeId>,
+}
+
+impl<T> Default for Forest<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Forest<T> {
+    ///
+    /// Creates a new `Forest` without any `Node`s.
+    ///
+    /// ```
+    /// use id_tree::Forest;
+    ///
+    /// let _forest: Forest<i32> = Forest::new();
+    /// ```
+    ///
+    pub fn new() -> Forest<T> {
+        Forest {
+            tree: Tree::new(),
+            roots: Vec::new(),
+        }
+    }
+
+    ///
+    /// Returns the `NodeId`s of the roots, in order.
+    ///
+    pub fn roots(&self) -> &[NodeId] {
+        &self.roots
+    }
+
+    ///
+    /// Returns the `Tree` holding the `Node`s of the `Forest`.  It doesn't have a root `Node`,
+    /// so the roots of the `Forest` are all orphans of it (in the order of their slots rather
+    /// than the order of the `Forest`).
+    ///
+    /// This gives access to everything a `Tree` offers for reading, such as the iterators over
+    /// the children or the ancestors of a `Node`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut forest: Forest<i32> = Forest::new();
+    /// let root_id = forest.insert(Node::new(0), AsNewRoot).unwrap();
+    /// forest.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// let tree = forest.as_tree();
+    /// assert_eq!(tree.root_node_id(), None);
+    /// assert_eq!(tree.children(&root_id).unwrap().next().unwrap().data(), &1);
+    /// ```
+    ///
+    pub fn as_tree(&self) -> &Tree<T> {
+        &self.tree
+    }
+
+    ///
+    /// Returns a `Result` containing an immutable reference to the `Node` identified by the given
+    /// `NodeId` or a `NodeIdError` if one occurred.
+    ///
+    pub fn get(&self, node_id: &NodeId) -> Result<&Node<T>, NodeIdError> {
+        self.tree.get(node_id)
+    }
+
+    ///
+    /// Returns a `Result` containing a mutable reference to the `Node` identified by the given
+    /// `NodeId` or a `NodeIdError` if one occurred.
+    ///
+    pub fn get_mut(&mut self, node_id: &NodeId) -> Result<&mut Node<T>, NodeIdError> {
+        self.tree.get_mut(node_id)
+    }
+
+    ///
+    /// Inserts a new `Node` into the `Forest`.  The `InsertBehavior` provided will determine
+    /// where the `Node` is inserted.
+    ///
+    /// `AsNewRoot` adds the `Node` as the last root.  `AsRoot` does the same, since there is no
+    /// single root `Node` it could take the place of.  `BeforeSibling` and `AfterSibling` also
+    /// work for roots and add the `Node` as a root next to them.
+    ///
+    /// Returns a `Result` containing the `NodeId` of the `Node` that was inserted or a
+    /// `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut forest: Forest<i32> = Forest::new();
+    /// let two_id = forest.insert(Node::new(2), AsNewRoot).unwrap();
+    /// let one_id = forest.insert(Node::new(1), BeforeSibling(&two_id)).unwrap();
+    ///
+    /// assert_eq!(forest.roots(), &[one_id, two_id]);
+    /// ```
+    ///
+    pub fn insert(
+        &mut self,
+        node: Node<T>,
+        behavior: InsertBehavior,
+    ) -> Result<NodeId, NodeIdError> {
+        match behavior {
+            InsertBehavior::AsRoot | InsertBehavior::AsNewRoot => {
+                let node_id = self.tree.insert_orphan(node);
+                self.roots.push(node_id.clone());
+                Ok(node_id)
+            }
+            InsertBehavior::BeforeSibling(sibling_id) => match self.root_position(sibling_id)? {
+                Some(position) => Ok(self.insert_root_at(node, position)),
+                None => self.tree.insert(node, behavior),
+            },
+            InsertBehavior::AfterSibling(sibling_id) => match self.root_position(sibling_id)? {
+                Some(position) => Ok(self.insert_root_at(node, position + 1)),
+                None => self.tree.insert(node, behavior),
+            },
+            _ => self.tree.insert(node, behavior),
+        }
+    }
+
+    ///
+    /// Removes a `Node` from the `Forest`.  The `RemoveBehavior` provided determines what happens
+    /// to the removed `Node`'s children.
+    ///
+    /// Children left without a parent become roots: in place of the removed `Node` if it was a
+    /// root, otherwise after the last root.
+    ///
+    /// Returns a `Result` containing the removed `Node` or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    /// use id_tree::RemoveBehavior::*;
+    ///
+    /// let mut forest: Forest<i32> = Forest::new();
+    /// let first_id = forest.insert(Node::new(0), AsNewRoot).unwrap();
+    /// let second_id = forest.insert(Node::new(1), AsNewRoot).unwrap();
+    /// let child_id = forest.insert(Node::new(2), UnderNode(&first_id)).unwrap();
+    ///
+    /// forest.remove_node(first_id, LiftChildren).unwrap();
+    /// assert_eq!(forest.roots(), &[child_id, second_id]);
+    /// ```
+    ///
+    pub fn remove_node(
+        &mut self,
+        node_id: NodeId,
+        behavior: RemoveBehavior,
+    ) -> Result<Node<T>, NodeIdError> {
+        let position = self.root_position(&node_id)?;
+        let children = self.tree.get(&node_id)?.children().clone();
+
+        let node = self.tree.remove_node(node_id, behavior)?;
+
+        match (behavior, position) {
+            (RemoveBehavior::DropChildren, Some(position)) => {
+                self.roots.remove(position);
+            }
+            (_, Some(position)) => {
+                self.roots.splice(position..position + 1, children);
+            }
+            (RemoveBehavior::OrphanChildren, None) => self.roots.extend(children),
+            (_, None) => {}
+        }
+
+        Ok(node)
+    }
+
+    ///
+    /// Moves a `Node` in the `Forest` to a new location based upon the `MoveBehavior` provided.
+    ///
+    /// `ToNewRoot` makes the `Node` the last root, unless it is a root already.  `ToRoot` does
+    /// the same.  If a root is moved below one of its own descendants with `ToParent`, the child
+    /// on the path between them takes its place amongst the roots.
+    ///
+    /// Returns an empty `Result` containing a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    /// use id_tree::MoveBehavior::*;
+    ///
+    /// let mut forest: Forest<i32> = Forest::new();
+    /// let first_id = forest.insert(Node::new(0), AsNewRoot).unwrap();
+    /// let second_id = forest.insert(Node::new(1), AsNewRoot).unwrap();
+    ///
+    /// forest.move_node(&first_id, ToParent(&second_id)).unwrap();
+    /// assert_eq!(forest.roots(), &[second_id.clone()]);
+    ///
+    /// forest.move_node(&first_id, ToNewRoot).unwrap();
+    /// assert_eq!(forest.roots(), &[second_id, first_id]);
+    /// ```
+    ///
+    pub fn move_node(
+        &mut self,
+        node_id: &NodeId,
+        behavior: MoveBehavior,
+    ) -> Result<(), NodeIdError> {
+        let position = self.root_position(node_id)?;
+
+        match behavior {
+            MoveBehavior::ToRoot | MoveBehavior::ToNewRoot => {
+                if position.is_none() {
+                    self.tree.orphan_node(node_id)?;
+                    self.roots.push(node_id.clone());
+                }
+            }
+            MoveBehavior::ToParent(parent_id) => {
+                self.tree.get(parent_id)?;
+                let replacement = match position {
+                    Some(_) => self.child_towards(node_id, parent_id),
+                    None => None,
+                };
+
+                self.tree.move_node(node_id, behavior)?;
+
+                if let Some(position) = position {
+                    match replacement {
+                        Some(replacement_id) => self.roots[position] = replacement_id,
+                        None => {
+                            self.roots.remove(position);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Returns a `PreOrderTraversal` iterator over the subtrees of all roots, one after another.
+    ///
+    pub fn traverse_pre_order(&self) -> PreOrderTraversal<'_, T> {
+        PreOrderTraversal::from_roots(&self.tree, &self.roots)
+    }
+
+    ///
+    /// Returns a `PreOrderTraversalIds` iterator over the subtrees of all roots, one after
+    /// another.
+    ///
+    pub fn traverse_pre_order_ids(&self) -> PreOrderTraversalIds<'_, T> {
+        PreOrderTraversalIds::from_roots(&self.tree, &self.roots)
+    }
+
+    ///
+    /// Returns a `PostOrderTraversal` iterator over the subtrees of all roots, one after another.
+    ///
+    pub fn traverse_post_order(&self) -> PostOrderTraversal<'_, T> {
+        PostOrderTraversal::from_roots(&self.tree, &self.roots)
+    }
+
+    ///
+    /// Returns a `PostOrderTraversalIds` iterator over the subtrees of all roots, one after
+    /// another.
+    ///
+    pub fn traverse_post_order_ids(&self) -> PostOrderTraversalIds<'_, T> {
+        PostOrderTraversalIds::from_roots(&self.tree, &self.roots)
+    }
+
+    ///
+    /// Returns a `LevelOrderTraversal` iterator over all roots, then all of their children and so
+    /// on.
+    ///
+    pub fn traverse_level_order(&self) -> LevelOrderTraversal<'_, T> {
+        LevelOrderTraversal::from_roots(&self.tree, &self.roots)
+    }
+
+    ///
+    /// Returns a `LevelOrderTraversalIds` iterator over all roots, then all of their children and
+    /// so on.
+    ///
+    pub fn traverse_level_order_ids(&self) -> LevelOrderTraversalIds<'_, T> {
+        LevelOrderTraversalIds::from_roots(&self.tree, &self.roots)
+    }
+
+    // Returns the position of a `Node` amongst the roots, or `None` if it has a parent.
+    fn root_position(&self, node_id: &NodeId) -> Result<Option<usize>, NodeIdError> {
+        if self.tree.get(node_id)?.parent().is_some() {
+            return Ok(None);
+        }
+        Ok(self.roots.iter().position(|root_id| root_id == node_id))
+    }
+
+    fn insert_root_at(&mut self, node: Node<T>, position: usize) -> NodeId {
+        let node_id = self.tree.insert_orphan(node);
+        self.roots.insert(position, node_id.clone());
+        node_id
+    }
+
+    // Returns the child of `upper_id` on the path down to `lower_id`, if `lower_id` is below it.
+    fn child_towards(&self, upper_id: &NodeId, lower_id: &NodeId) -> Option<NodeId> {
+        let mut current_id = lower_id;
+        while let Some(parent_id) = self.tree.get(current_id).ok()?.parent() {
+            if parent_id == upper_id {
+                return Some(current_id.clone());
+            }
+            current_id = parent_id;
+        }
+        None
+    }
+}
+
+impl<T: Clone> Clone for Forest<T> {
+    fn clone(&self) -> Self {
+        let tree = self.tree.clone();
+        let roots = self
+            .roots
+            .iter()
+            .map(|root_id| {
+                tree.translate_node_id(root_id)
+                    .expect("Forest::clone: The roots are always in the Tree.")
+            })
+            .collect();
+
+        Forest { tree, roots }
+    }
+}
+
+impl<T> From<Tree<T>> for Forest<T> {
+    ///
+    /// Turns a `Tree` into a `Forest` whose roots are the root `Node` followed by the orphaned
+    /// `Node`s of the `Tree` (see `Tree::forest_roots`).  All `NodeId`s of the `Tree` are valid
+    /// for the new `Forest`.
+    ///
+    fn from(mut tree: Tree<T>) -> Forest<T> {
+        let roots = tree.forest_roots().collect();
+        tree.root = None;
+        Forest { tree, roots }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Forest;
+    use InsertBehavior::*;
+    use MoveBehavior::*;
+    use Node;
+    use NodeIdError;
+    use RemoveBehavior::*;
+    use Tree;
+
+    fn data(forest: &Forest<i32>) -> Vec<i32> {
+        forest
+            .traverse_pre_order()
+            .map(|node| *node.data())
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_and_traverse() {
+        let mut forest = Forest::new();
+        let a = forest.insert(Node::new(0), AsNewRoot).unwrap();
+        let c = forest.insert(Node::new(3), AsRoot).unwrap();
+        let b = forest.insert(Node::new(2), BeforeSibling(&c)).unwrap();
+        forest.insert(Node::new(1), UnderNode(&a)).unwrap();
+        let d = forest.insert(Node::new(4), AfterSibling(&c)).unwrap();
+        forest.insert(Node::new(5), UnderNode(&b)).unwrap();
+
+        assert_eq!(forest.roots(), &[a.clone(), b.clone(), c, d.clone()]);
+        assert_eq!(data(&forest), vec![0, 1, 2, 5, 3, 4]);
+
+        let post_order: Vec<i32> = forest
+            .traverse_post_order()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(post_order, vec![1, 0, 5, 2, 3, 4]);
+
+        let level_order: Vec<i32> = forest
+            .traverse_level_order()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(level_order, vec![0, 2, 3, 4, 1, 5]);
+
+        assert_eq!(forest.traverse_pre_order_ids().count(), 6);
+        let post_order_ids: Vec<_> = forest.traverse_post_order_ids().collect();
+        assert_eq!(post_order_ids.len(), 6);
+        assert_eq!(post_order_ids[1], a);
+        assert_eq!(post_order_ids[5], d);
+        assert_eq!(forest.traverse_level_order_ids().next(), Some(a));
+        assert_eq!(forest.as_tree().check_integrity(), Ok(()));
+    }
+
+    #[test]
+    // quadratic when every insertion checks the whole Tree
+    #[cfg(not(all(feature = "integrity_checks", debug_assertions)))]
+    fn test_traverse_deep_forest() {
+        let mut forest = Forest::new();
+        for root in 0..2 {
+            let mut parent_id = forest.insert(Node::new(root), AsNewRoot).unwrap();
+            for i in 1..100_000 {
+                parent_id = forest.insert(Node::new(i), UnderNode(&parent_id)).unwrap();
+            }
+        }
+
+        assert_eq!(forest.traverse_post_order().count(), 200_000);
+        let last = forest.traverse_post_order_ids().last();
+        assert_eq!(last.as_ref(), Some(&forest.roots()[1]));
+    }
+
+    #[test]
+    fn test_remove_and_move() {
+        let mut forest = Forest::new();
+        let a = forest.insert(Node::new(0), AsNewRoot).unwrap();
+        let b = forest.insert(Node::new(1), UnderNode(&a)).unwrap();
+        let c = forest.insert(Node::new(2), UnderNode(&b)).unwrap();
+        let d = forest.insert(Node::new(3), UnderNode(&b)).unwrap();
+        let e = forest.insert(Node::new(4), AsNewRoot).unwrap();
+
+        // a root moved below its own descendant is replaced by the child in between
+        forest.move_node(&a, ToParent(&c)).unwrap();
+        assert_eq!(forest.roots(), &[b.clone(), e.clone()]);
+        assert_eq!(data(&forest), vec![1, 2, 0, 3, 4]);
+
+        forest.move_node(&c, ToNewRoot).unwrap();
+        forest.move_node(&e, ToNewRoot).unwrap();
+        assert_eq!(forest.roots(), &[b.clone(), e.clone(), c.clone()]);
+
+        forest.move_node(&e, ToParent(&d)).unwrap();
+        assert_eq!(forest.roots(), &[b.clone(), c.clone()]);
+
+        forest.remove_node(b, LiftChildren).unwrap();
+        assert_eq!(forest.roots(), &[d.clone(), c.clone()]);
+
+        forest.remove_node(e, OrphanChildren).unwrap();
+        forest.remove_node(c.clone(), OrphanChildren).unwrap();
+        assert_eq!(forest.roots(), &[d.clone(), a.clone()]);
+
+        forest.remove_node(d.clone(), DropChildren).unwrap();
+        assert_eq!(forest.roots(), &[a.clone()][..]);
+        assert_eq!(forest.as_tree().check_integrity(), Ok(()));
+
+        assert_eq!(
+            forest.move_node(&c, ToNewRoot),
+            Err(NodeIdError::NodeIdNoLongerValid)
+        );
+        assert_eq!(
+            forest.insert(Node::new(5), BeforeSibling(&d)),
+            Err(NodeIdError::NodeIdNoLongerValid)
+        );
+        let other: Forest<i32> = Forest::new();
+        assert_eq!(
+            other.get(&a).unwrap_err(),
+            NodeIdError::InvalidNodeIdForTree
+        );
+    }
+
+    #[test]
+    fn test_from_tree_and_clone() {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let grandchild_id = tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+        tree.remove_node(child_id, OrphanChildren).unwrap();
+
+        let forest = Forest::from(tree);
+        assert_eq!(forest.roots(), &[root_id.clone(), grandchild_id]);
+        assert_eq!(forest.as_tree().root_node_id(), None);
+
+        let clone = forest.clone();
+        assert_eq!(data(&clone), vec![0, 2]);
+        assert!(clone.get(&root_id).is_err());
+        assert_eq!(clone.get(&clone.roots()[0]).unwrap().data(), &0);
+    }
+}
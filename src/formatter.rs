@@ -0,0 +1,366 @@
+use std::fmt;
+use std::fmt::Display;
+
+use FormatError;
+use NodeId;
+use Tree;
+
+struct Style {
+    branch: &'static str,
+    last_branch: &'static str,
+    pipe: &'static str,
+    blank: &'static str,
+    ellipsis: &'static str,
+}
+
+const UNICODE: Style = Style {
+    branch: "├── ",
+    last_branch: "└── ",
+    pipe: "│   ",
+    blank: "    ",
+    ellipsis: "…",
+};
+
+const ASCII: Style = Style {
+    branch: "|-- ",
+    last_branch: "`-- ",
+    pipe: "|   ",
+    blank: "    ",
+    ellipsis: "...",
+};
+
+///
+/// A configurable writer for the box-drawing text view of a `Tree` (see `Tree::write_formatted`).
+///
+/// ```
+/// use id_tree::*;
+/// use id_tree::InsertBehavior::*;
+///
+/// let mut tree: Tree<i32> = Tree::new();
+/// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+/// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+/// for i in 2..6 {
+///     tree.insert(Node::new(i), UnderNode(&child_id)).unwrap();
+/// }
+/// tree.insert(Node::new(6), UnderNode(&root_id)).unwrap();
+///
+/// let formatter = TreeFormatter::new()
+///     .with_ascii(true)
+///     .with_max_children(2);
+///
+/// let mut s = String::new();
+/// formatter.write(&tree, &mut s).unwrap();
+/// assert_eq!(&s, "\
+/// 0
+/// |-- 1
+/// |   |-- 2
+/// |   |-- 3
+/// |   `-- ...2 more
+/// `-- 6
+/// ");
+/// ```
+///
+pub struct TreeFormatter<'a, T> {
+    label: Box<dyn Fn(&T) -> String + 'a>,
+    start: Option<NodeId>,
+    ascii: bool,
+    max_depth: Option<usize>,
+    max_children: Option<usize>,
+    node_ids: bool,
+}
+
+impl<'a, T: Display> Default for TreeFormatter<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> TreeFormatter<'a, T> {
+    ///
+    /// Creates a new `TreeFormatter` that labels every `Node` with the `Display` representation
+    /// of its data.
+    ///
+    /// ```
+    /// use id_tree::TreeFormatter;
+    ///
+    /// let _formatter: TreeFormatter<i32> = TreeFormatter::new();
+    /// ```
+    ///
+    pub fn new() -> TreeFormatter<'a, T>
+    where
+        T: Display,
+    {
+        TreeFormatter::from_label_fn(|data: &T| data.to_string())
+    }
+
+    ///
+    /// Creates a new `TreeFormatter` that labels every `Node` with the `String` returned by the
+    /// given function.
+    ///
+    /// ```
+    /// use id_tree::TreeFormatter;
+    ///
+    /// let _formatter: TreeFormatter<i32> =
+    ///     TreeFormatter::from_label_fn(|data: &i32| format!("{:#x}", data));
+    /// ```
+    ///
+    pub fn from_label_fn<F>(label: F) -> TreeFormatter<'a, T>
+    where
+        F: Fn(&T) -> String + 'a,
+    {
+        TreeFormatter {
+            label: Box::new(label),
+            start: None,
+            ascii: false,
+            max_depth: None,
+            max_children: None,
+            node_ids: false,
+        }
+    }
+
+    ///
+    /// Sets the `Node` to start from.  Only this `Node` and its descendants are written.
+    ///
+    /// Defaults to the root `Node` of the `Tree`.
+    ///
+    pub fn with_start(mut self, node_id: &NodeId) -> TreeFormatter<'a, T> {
+        self.start = Some(node_id.clone());
+        self
+    }
+
+    ///
+    /// Sets whether only ASCII characters are used to draw the branches.
+    ///
+    /// Defaults to `false`.
+    ///
+    pub fn with_ascii(mut self, ascii: bool) -> TreeFormatter<'a, T> {
+        self.ascii = ascii;
+        self
+    }
+
+    ///
+    /// Sets the maximum depth (relative to the `Node` to start from) of the `Node`s that are
+    /// written.  The children of `Node`s at that depth are replaced by a single "…N more" line.
+    ///
+    /// Defaults to no limit.
+    ///
+    pub fn with_max_depth(mut self, max_depth: usize) -> TreeFormatter<'a, T> {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    ///
+    /// Sets the maximum number of children that are written for each `Node`.  The remaining
+    /// children are replaced by a single "…N more" line.
+    ///
+    /// Defaults to no limit.
+    ///
+    pub fn with_max_children(mut self, max_children: usize) -> TreeFormatter<'a, T> {
+        self.max_children = Some(max_children);
+        self
+    }
+
+    ///
+    /// Sets whether the index of each `NodeId` is put in front of the label of its `Node`.
+    ///
+    /// Defaults to `false`.
+    ///
+    pub fn with_node_ids(mut self, node_ids: bool) -> TreeFormatter<'a, T> {
+        self.node_ids = node_ids;
+        self
+    }
+
+    ///
+    /// Writes the `Tree` (or the part of it below the `Node` to start from).  Writes nothing if
+    /// the `Tree` is empty and no `Node` to start from was set.
+    ///
+    /// Returns a `FormatError` if the `NodeId` to start from can't be used on the `Tree` or if
+    /// the writer returns an error.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+    ///
+    /// let formatter = TreeFormatter::new()
+    ///     .with_start(&child_id)
+    ///     .with_node_ids(true);
+    ///
+    /// let mut s = String::new();
+    /// formatter.write(&tree, &mut s).unwrap();
+    /// assert_eq!(&s, "\
+    /// 1: 1
+    /// └── 2: 2
+    /// ");
+    /// ```
+    ///
+    pub fn write<W: fmt::Write>(&self, tree: &Tree<T>, w: &mut W) -> Result<(), FormatError> {
+        let start = match self.start {
+            Some(ref node_id) => {
+                tree.get(node_id)?;
+                node_id
+            }
+            None => match tree.root_node_id() {
+                Some(root_id) => root_id,
+                None => return Ok(()),
+            },
+        };
+
+        let style = if self.ascii { &ASCII } else { &UNICODE };
+
+        self.write_label(w, tree, start)?;
+
+        // one entry for every `Node` whose children are being written: its children, the index
+        // of the next child to write, the number of children to write, the depth of the
+        // children and the length `prefix` had before the `Node` itself was written.
+        let mut stack = Vec::new();
+        let mut prefix = String::new();
+        stack.push(self.frame(tree, start, 1, 0));
+
+        while let Some(&mut (children, ref mut next, shown, depth, prefix_len)) = stack.last_mut() {
+            if *next < shown {
+                let child_id = &children[*next];
+                *next += 1;
+
+                let is_last = *next == children.len();
+                write!(w, "{}", prefix)?;
+                w.write_str(if is_last {
+                    style.last_branch
+                } else {
+                    style.branch
+                })?;
+                self.write_label(w, tree, child_id)?;
+
+                let child_prefix_len = prefix.len();
+                prefix.push_str(if is_last { style.blank } else { style.pipe });
+                stack.push(self.frame(tree, child_id, depth + 1, child_prefix_len));
+            } else {
+                if shown < children.len() {
+                    writeln!(
+                        w,
+                        "{}{}{}{} more",
+                        prefix,
+                        style.last_branch,
+                        style.ellipsis,
+                        children.len() - shown
+                    )?;
+                }
+                prefix.truncate(prefix_len);
+                stack.pop();
+            }
+        }
+
+        Ok(())
+    }
+
+    // Everything needed to write the children of a `Node` at the given depth.
+    fn frame<'t>(
+        &self,
+        tree: &'t Tree<T>,
+        node_id: &NodeId,
+        depth: usize,
+        prefix_len: usize,
+    ) -> (&'t [NodeId], usize, usize, usize, usize) {
+        let children = tree.get_unsafe(node_id).children();
+
+        let mut shown = children.len();
+        if let Some(max_children) = self.max_children {
+            shown = shown.min(max_children);
+        }
+        if let Some(max_depth) = self.max_depth {
+            if depth > max_depth {
+                shown = 0;
+            }
+        }
+
+        (children, 0, shown, depth, prefix_len)
+    }
+
+    fn write_label<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        tree: &Tree<T>,
+        node_id: &NodeId,
+    ) -> fmt::Result {
+        if self.node_ids {
+            write!(w, "{}: ", node_id.index)?;
+        }
+        writeln!(w, "{}", (self.label)(tree.get_unsafe(node_id).data()))
+    }
+}
+
+impl<'a, T> fmt::Debug for TreeFormatter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TreeFormatter")
+            .field("start", &self.start)
+            .field("ascii", &self.ascii)
+            .field("max_depth", &self.max_depth)
+            .field("max_children", &self.max_children)
+            .field("node_ids", &self.node_ids)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::TreeFormatter;
+    use InsertBehavior::*;
+    use Node;
+    use Tree;
+
+    #[test]
+    fn test_max_depth() {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let first_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let second_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(3), UnderNode(&first_id)).unwrap();
+        tree.insert(Node::new(4), UnderNode(&second_id)).unwrap();
+        tree.insert(Node::new(5), UnderNode(&second_id)).unwrap();
+
+        let mut s = String::new();
+        TreeFormatter::new()
+            .with_max_depth(1)
+            .write(&tree, &mut s)
+            .unwrap();
+        assert_eq!(&s, "0\n├── 1\n│   └── …1 more\n└── 2\n    └── …2 more\n");
+
+        let mut s = String::new();
+        TreeFormatter::new()
+            .with_max_depth(0)
+            .write(&tree, &mut s)
+            .unwrap();
+        assert_eq!(&s, "0\n└── …2 more\n");
+
+        let mut s = String::new();
+        TreeFormatter::new()
+            .with_max_children(0)
+            .with_ascii(true)
+            .write(&tree, &mut s)
+            .unwrap();
+        assert_eq!(&s, "0\n`-- ...2 more\n");
+    }
+
+    #[test]
+    // quadratic when every insertion checks the whole Tree
+    #[cfg(not(all(feature = "integrity_checks", debug_assertions)))]
+    fn test_deep_tree() {
+        let mut tree = Tree::new();
+        let mut node_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        for i in 1..100_000 {
+            node_id = tree.insert(Node::new(i), UnderNode(&node_id)).unwrap();
+        }
+
+        let mut s = String::new();
+        TreeFormatter::new()
+            .with_max_depth(2)
+            .write(&tree, &mut s)
+            .unwrap();
+        assert_eq!(&s, "0\n└── 1\n    └── 2\n        └── …1 more\n");
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+
+use snowflake::ProcessUniqueId;
+use IntegrityError;
+use Node;
+use NodeId;
+
+// Checks the internal structure of a `Tree` given by its parts.  Nothing in here trusts the
+// parts to be consistent: no `NodeId` is used before it has been checked, so this can be run on
+// untrusted input (for example a deserialized `Tree`).
+pub(crate) fn check<T>(
+    tree_id: ProcessUniqueId,
+    root: Option<&NodeId>,
+    nodes: &[Option<Node<T>>],
+    generations: &[usize],
+    free_ids: &[NodeId],
+) -> Result<(), IntegrityError> {
+    if nodes.len() != generations.len() {
+        return Err(IntegrityError::GenerationsLength);
+    }
+
+    let check_id = |node_id: &NodeId| -> Result<&Node<T>, IntegrityError> {
+        if node_id.tree_id != tree_id {
+            return Err(IntegrityError::ForeignNodeId(node_id.clone()));
+        }
+        match nodes.get(node_id.index) {
+            Some(Some(node)) if generations[node_id.index] == node_id.generation => Ok(node),
+            _ => Err(IntegrityError::DanglingNodeId(node_id.clone())),
+        }
+    };
+
+    if let Some(root_id) = root {
+        if check_id(root_id)?.parent().is_some() {
+            return Err(IntegrityError::RootHasParent(root_id.clone()));
+        }
+    }
+
+    for (index, node) in nodes.iter().enumerate() {
+        let node = match *node {
+            Some(ref node) => node,
+            None => continue,
+        };
+        let node_id = NodeId {
+            tree_id,
+            index,
+            generation: generations[index],
+        };
+
+        for child_id in node.children() {
+            if check_id(child_id)?.parent() != Some(&node_id) {
+                return Err(IntegrityError::ParentMismatch {
+                    parent: node_id,
+                    child: child_id.clone(),
+                });
+            }
+        }
+
+        if let Some(parent_id) = node.parent() {
+            let count = check_id(parent_id)?
+                .children()
+                .iter()
+                .filter(|child_id| **child_id == node_id)
+                .count();
+            if count != 1 {
+                return Err(IntegrityError::ChildrenMismatch {
+                    parent: parent_id.clone(),
+                    child: node_id,
+                });
+            }
+        }
+    }
+
+    // every parent link has been checked now, so the ancestors of each `Node` can be followed
+    // safely.  `walks[i]` is the (1-based) index of the walk that first went through slot `i`,
+    // running into a slot of the same walk again means there is a cycle.
+    let mut walks = vec![0; nodes.len()];
+    for index in 0..nodes.len() {
+        let walk = index + 1;
+        let mut current = index;
+        while let Some(ref node) = nodes[current] {
+            if walks[current] == walk {
+                return Err(IntegrityError::Cycle(NodeId {
+                    tree_id,
+                    index: current,
+                    generation: generations[current],
+                }));
+            }
+            if walks[current] != 0 {
+                // an earlier walk went on from here without finding a cycle
+                break;
+            }
+            walks[current] = walk;
+            match node.parent() {
+                Some(parent_id) => current = parent_id.index,
+                None => break,
+            }
+        }
+    }
+
+    let mut free = HashSet::with_capacity(free_ids.len());
+    for free_id in free_ids {
+        if free_id.tree_id != tree_id {
+            return Err(IntegrityError::ForeignNodeId(free_id.clone()));
+        }
+        let matches = match nodes.get(free_id.index) {
+            Some(&None) => generations[free_id.index] == free_id.generation,
+            _ => false,
+        };
+        if !matches || !free.insert(free_id.index) {
+            return Err(IntegrityError::FreeIdsMismatch(free_id.index));
+        }
+    }
+    if let Some(index) =
+        (0..nodes.len()).find(|index| nodes[*index].is_none() && !free.contains(index))
+    {
+        return Err(IntegrityError::FreeIdsMismatch(index));
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "serde_support"))]
+mod tests {
+
+    extern crate serde_json;
+
+    use self::serde_json::Value;
+    use InsertBehavior::*;
+    use IntegrityError::*;
+    use Node;
+    use RemoveBehavior::*;
+    use Tree;
+
+    // 0 -> [1 -> [3], 2], with the slot of a removed `Node` at index 4
+    fn sample() -> Value {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let first_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(3), UnderNode(&first_id)).unwrap();
+        let removed_id = tree.insert(Node::new(4), UnderNode(&first_id)).unwrap();
+        tree.remove_node(removed_id, DropChildren).unwrap();
+
+        serde_json::to_value(&tree).unwrap()
+    }
+
+    fn error_for(value: Value) -> String {
+        serde_json::from_value::<Tree<i32>>(value)
+            .expect_err("a broken Tree was accepted")
+            .to_string()
+    }
+
+    #[test]
+    fn test_valid() {
+        let tree: Tree<i32> = serde_json::from_value(sample()).unwrap();
+        assert_eq!(tree.nodes.len(), 5);
+        assert_eq!(tree.free_ids.len(), 1);
+    }
+
+    #[test]
+    fn test_broken_links() {
+        let mut value = sample();
+        value["nodes"][1]["children"][0]["index"] = Value::from(17);
+        assert!(error_for(value).contains("doesn't point to a Node"));
+
+        let mut value = sample();
+        value["nodes"][1]["children"][0]["generation"] = Value::from(1);
+        assert!(error_for(value).contains("doesn't point to a Node"));
+
+        let mut value = sample();
+        value["nodes"][3]["parent"] = value["nodes"][1]["parent"].clone();
+        assert!(error_for(value).contains("isn't its parent"));
+
+        let mut value = sample();
+        let child = value["nodes"][0]["children"][0].clone();
+        value["nodes"][0]["children"]
+            .as_array_mut()
+            .unwrap()
+            .push(child);
+        assert!(error_for(value).contains("exactly once"));
+
+        let mut value = sample();
+        value["root"]["index"] = Value::from(1);
+        assert!(error_for(value).contains("has a parent"));
+    }
+
+    #[test]
+    fn test_cycle() {
+        // 1 and 3 become each other's only child and parent, away from the root
+        let mut value = sample();
+        let one = value["nodes"][0]["children"][0].clone();
+        let three = value["nodes"][1]["children"][0].clone();
+        value["nodes"][0]["children"] = Value::from(vec![value["nodes"][0]["children"][1].clone()]);
+        value["nodes"][1]["parent"] = three;
+        value["nodes"][3]["children"] = Value::from(vec![one]);
+        assert!(error_for(value).contains("one of its own ancestors"));
+    }
+
+    #[test]
+    fn test_foreign_ids() {
+        let tree: Tree<i32> = serde_json::from_value(sample()).unwrap();
+        let foreign_id = Tree::<i32>::new().id;
+
+        let result = super::check(
+            foreign_id,
+            tree.root.as_ref(),
+            &tree.nodes,
+            &tree.generations,
+            &tree.free_ids,
+        );
+        assert_eq!(result, Err(ForeignNodeId(tree.root.clone().unwrap())));
+    }
+
+    #[test]
+    fn test_free_ids() {
+        let mut value = sample();
+        value["free_ids"] = Value::from(Vec::<Value>::new());
+        assert!(error_for(value).contains("empty slots at index 4"));
+
+        let mut value = sample();
+        value["free_ids"][0]["index"] = Value::from(2);
+        assert!(error_for(value).contains("empty slots at index 2"));
+
+        let mut value = sample();
+        value["generations"].as_array_mut().unwrap().pop();
+        assert!(error_for(value).contains("one generation for each slot"));
+    }
+
+    #[test]
+    fn test_error_value() {
+        let tree: Tree<i32> = serde_json::from_value(sample()).unwrap();
+        let root_id = tree.root.clone().unwrap();
+        let mut nodes = tree.nodes;
+        let child_id = nodes[0].as_ref().unwrap().children[0].clone();
+        nodes[0].as_mut().unwrap().parent = Some(child_id);
+
+        let result = super::check(
+            tree.id,
+            Some(&root_id),
+            &nodes,
+            &tree.generations,
+            &tree.free_ids,
+        );
+        assert_eq!(result, Err(RootHasParent(root_id)));
+    }
+}
@@ -1,4 +1,6 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
+use std::iter::{Chain, Enumerate, Rev};
+use std::marker::PhantomData;
 use std::slice::Iter;
 use std::vec::IntoIter;
 
@@ -26,7 +28,7 @@ pub struct Ancestors<'a, T: 'a> {
 impl<'a, T> Ancestors<'a, T> {
     pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> Ancestors<'a, T> {
         Ancestors {
-            tree: tree,
+            tree,
             node_id: Some(node_id),
         }
     }
@@ -51,7 +53,7 @@ impl<'a, T> Iterator for Ancestors<'a, T> {
 impl<'a, T> Clone for Ancestors<'a, T> {
     fn clone(&self) -> Self {
         Ancestors {
-            tree: &self.tree,
+            tree: self.tree,
             node_id: self.node_id.clone(),
         }
     }
@@ -70,7 +72,7 @@ pub struct AncestorIds<'a, T: 'a> {
 impl<'a, T> AncestorIds<'a, T> {
     pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> AncestorIds<'a, T> {
         AncestorIds {
-            tree: tree,
+            tree,
             node_id: Some(node_id),
         }
     }
@@ -80,22 +82,18 @@ impl<'a, T> Iterator for AncestorIds<'a, T> {
     type Item = &'a NodeId;
 
     fn next(&mut self) -> Option<&'a NodeId> {
-        self.node_id
-            .take()
-            .and_then(|current_id| self.tree.get(&current_id).ok())
-            .and_then(|node_ref| node_ref.parent())
-            .and_then(|parent_id| {
-                self.node_id = Some(parent_id.clone());
+        let current_id = self.node_id.take()?;
+        let parent_id = self.tree.get(&current_id).ok()?.parent()?;
+        self.node_id = Some(parent_id.clone());
 
-                Some(parent_id)
-            })
+        Some(parent_id)
     }
 }
 
 impl<'a, T> Clone for AncestorIds<'a, T> {
     fn clone(&self) -> Self {
         AncestorIds {
-            tree: &self.tree,
+            tree: self.tree,
             node_id: self.node_id.clone(),
         }
     }
@@ -115,7 +113,7 @@ pub struct Children<'a, T: 'a> {
 impl<'a, T> Children<'a, T> {
     pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> Children<'a, T> {
         Children {
-            tree: tree,
+            tree,
             child_ids: tree.get_unsafe(&node_id).children().as_slice().iter(),
         }
     }
@@ -134,7 +132,7 @@ impl<'a, T> Iterator for Children<'a, T> {
 impl<'a, T> Clone for Children<'a, T> {
     fn clone(&self) -> Self {
         Children {
-            tree: &self.tree,
+            tree: self.tree,
             child_ids: self.child_ids.clone(),
         }
     }
@@ -166,6 +164,206 @@ impl<'a> Iterator for ChildrenIds<'a> {
     }
 }
 
+///
+/// An Iterator over the siblings of a `Node`.
+///
+/// Iterates over the other children of the parent of a given `Node`, in order.  Each call to
+/// `next` will return an immutable reference to the next sibling `Node`.
+///
+pub struct Siblings<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    sibling_ids: SiblingIds<'a>,
+}
+
+impl<'a, T> Siblings<'a, T> {
+    pub(crate) fn new(
+        tree: &'a Tree<T>,
+        preceding: &'a [NodeId],
+        following: &'a [NodeId],
+    ) -> Siblings<'a, T> {
+        Siblings {
+            tree,
+            sibling_ids: SiblingIds::new(preceding, following),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Siblings<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<&'a Node<T>> {
+        self.sibling_ids
+            .next()
+            .and_then(|sibling_id| self.tree.get(sibling_id).ok())
+    }
+}
+
+impl<'a, T> Clone for Siblings<'a, T> {
+    fn clone(&self) -> Self {
+        Siblings {
+            tree: self.tree,
+            sibling_ids: self.sibling_ids.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the siblings of a `Node`.
+///
+/// Iterates over `NodeId`s instead of over the `Node`s themselves.
+///
+#[derive(Clone)]
+pub struct SiblingIds<'a> {
+    sibling_ids: Chain<Iter<'a, NodeId>, Iter<'a, NodeId>>,
+}
+
+impl<'a> SiblingIds<'a> {
+    pub(crate) fn new(preceding: &'a [NodeId], following: &'a [NodeId]) -> SiblingIds<'a> {
+        SiblingIds {
+            sibling_ids: preceding.iter().chain(following.iter()),
+        }
+    }
+}
+
+impl<'a> Iterator for SiblingIds<'a> {
+    type Item = &'a NodeId;
+
+    fn next(&mut self) -> Option<&'a NodeId> {
+        self.sibling_ids.next()
+    }
+}
+
+///
+/// An Iterator over the siblings that come after a `Node`.
+///
+/// Iterates over the siblings of a given `Node` that follow it, starting with the next one.  Each
+/// call to `next` will return an immutable reference to the next sibling `Node`.
+///
+pub struct FollowingSiblings<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    sibling_ids: FollowingSiblingIds<'a>,
+}
+
+impl<'a, T> FollowingSiblings<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, following: &'a [NodeId]) -> FollowingSiblings<'a, T> {
+        FollowingSiblings {
+            tree,
+            sibling_ids: FollowingSiblingIds::new(following),
+        }
+    }
+}
+
+impl<'a, T> Iterator for FollowingSiblings<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<&'a Node<T>> {
+        self.sibling_ids
+            .next()
+            .and_then(|sibling_id| self.tree.get(sibling_id).ok())
+    }
+}
+
+impl<'a, T> Clone for FollowingSiblings<'a, T> {
+    fn clone(&self) -> Self {
+        FollowingSiblings {
+            tree: self.tree,
+            sibling_ids: self.sibling_ids.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the siblings that come after a `Node`.
+///
+/// Iterates over `NodeId`s instead of over the `Node`s themselves.
+///
+#[derive(Clone)]
+pub struct FollowingSiblingIds<'a> {
+    sibling_ids: Iter<'a, NodeId>,
+}
+
+impl<'a> FollowingSiblingIds<'a> {
+    pub(crate) fn new(following: &'a [NodeId]) -> FollowingSiblingIds<'a> {
+        FollowingSiblingIds {
+            sibling_ids: following.iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for FollowingSiblingIds<'a> {
+    type Item = &'a NodeId;
+
+    fn next(&mut self) -> Option<&'a NodeId> {
+        self.sibling_ids.next()
+    }
+}
+
+///
+/// An Iterator over the siblings that come before a `Node`.
+///
+/// Iterates over the siblings of a given `Node` that precede it, starting with the previous one
+/// (so in reverse order).  Each call to `next` will return an immutable reference to the next
+/// sibling `Node`.
+///
+pub struct PrecedingSiblings<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    sibling_ids: PrecedingSiblingIds<'a>,
+}
+
+impl<'a, T> PrecedingSiblings<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, preceding: &'a [NodeId]) -> PrecedingSiblings<'a, T> {
+        PrecedingSiblings {
+            tree,
+            sibling_ids: PrecedingSiblingIds::new(preceding),
+        }
+    }
+}
+
+impl<'a, T> Iterator for PrecedingSiblings<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<&'a Node<T>> {
+        self.sibling_ids
+            .next()
+            .and_then(|sibling_id| self.tree.get(sibling_id).ok())
+    }
+}
+
+impl<'a, T> Clone for PrecedingSiblings<'a, T> {
+    fn clone(&self) -> Self {
+        PrecedingSiblings {
+            tree: self.tree,
+            sibling_ids: self.sibling_ids.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the siblings that come before a `Node`.
+///
+/// Iterates over `NodeId`s instead of over the `Node`s themselves.
+///
+#[derive(Clone)]
+pub struct PrecedingSiblingIds<'a> {
+    sibling_ids: Rev<Iter<'a, NodeId>>,
+}
+
+impl<'a> PrecedingSiblingIds<'a> {
+    pub(crate) fn new(preceding: &'a [NodeId]) -> PrecedingSiblingIds<'a> {
+        PrecedingSiblingIds {
+            sibling_ids: preceding.iter().rev(),
+        }
+    }
+}
+
+impl<'a> Iterator for PrecedingSiblingIds<'a> {
+    type Item = &'a NodeId;
+
+    fn next(&mut self) -> Option<&'a NodeId> {
+        self.sibling_ids.next()
+    }
+}
+
 ///
 /// An Iterator over the sub-tree relative to a given `Node`.
 ///
@@ -178,16 +376,21 @@ pub struct PreOrderTraversal<'a, T: 'a> {
 }
 
 impl<'a, T> PreOrderTraversal<'a, T> {
-    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> PreOrderTraversal<T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> PreOrderTraversal<'a, T> {
         // over allocating, but all at once instead of re-sizing and re-allocating as we go
         let mut data = VecDeque::with_capacity(tree.capacity());
 
         data.push_front(node_id);
 
-        PreOrderTraversal {
-            tree: tree,
-            data: data,
-        }
+        PreOrderTraversal { tree, data }
+    }
+
+    // Visits the subtrees below the given `Node`s one after another.
+    pub(crate) fn from_roots(tree: &'a Tree<T>, root_ids: &[NodeId]) -> PreOrderTraversal<'a, T> {
+        let mut data = VecDeque::with_capacity(tree.capacity());
+        data.extend(root_ids.iter().cloned());
+
+        PreOrderTraversal { tree, data }
     }
 }
 
@@ -195,199 +398,775 @@ impl<'a, T> Iterator for PreOrderTraversal<'a, T> {
     type Item = &'a Node<T>;
 
     fn next(&mut self) -> Option<&'a Node<T>> {
-        self.data
-            .pop_front()
-            .and_then(|node_id| self.tree.get(&node_id).ok())
-            .and_then(|node_ref| {
+        let node_id = self.data.pop_front()?;
+        let node_ref = self.tree.get(&node_id).ok()?;
+
+        // prepend child_ids
+        for child_id in node_ref.children().iter().rev() {
+            self.data.push_front(child_id.clone());
+        }
+
+        Some(node_ref)
+    }
+}
+
+impl<'a, T> Clone for PreOrderTraversal<'a, T> {
+    fn clone(&self) -> Self {
+        PreOrderTraversal {
+            tree: self.tree,
+            data: self.data.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the sub-tree relative to a given `Node`.
+///
+/// Iterates over all of the `NodeIds`s in the sub-tree of a given `NodeId` in the `Tree`.  Each call to
+/// `next` will return the next `NodeId` in Pre-Order Traversal order.
+///
+pub struct PreOrderTraversalIds<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    data: VecDeque<NodeId>,
+}
+
+impl<'a, T> PreOrderTraversalIds<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> PreOrderTraversalIds<'a, T> {
+        // over allocating, but all at once instead of re-sizing and re-allocating as we go
+        let mut data = VecDeque::with_capacity(tree.capacity());
+
+        data.push_front(node_id);
+
+        PreOrderTraversalIds { tree, data }
+    }
+
+    // Visits the subtrees below the given `Node`s one after another.
+    pub(crate) fn from_roots(
+        tree: &'a Tree<T>,
+        root_ids: &[NodeId],
+    ) -> PreOrderTraversalIds<'a, T> {
+        let mut data = VecDeque::with_capacity(tree.capacity());
+        data.extend(root_ids.iter().cloned());
+
+        PreOrderTraversalIds { tree, data }
+    }
+}
+
+impl<'a, T> Iterator for PreOrderTraversalIds<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        self.data.pop_front().and_then(|node_id| {
+            self.tree.get(&node_id).ok().map(|node_ref| {
                 // prepend child_ids
                 for child_id in node_ref.children().iter().rev() {
                     self.data.push_front(child_id.clone());
                 }
-
-                Some(node_ref)
-            })
+
+                node_id
+            })
+        })
+    }
+}
+
+impl<'a, T> Clone for PreOrderTraversalIds<'a, T> {
+    fn clone(&self) -> Self {
+        PreOrderTraversalIds {
+            tree: self.tree,
+            data: self.data.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the sub-tree relative to a given `Node`.
+///
+/// Iterates over all of the `Node`s in the sub-tree of a given `Node` in the `Tree`.  Each call to
+/// `next` will return an immutable reference to the next `Node` in Post-Order Traversal order.
+///
+pub struct PostOrderTraversal<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    ids: IntoIter<NodeId>,
+}
+
+impl<'a, T> PostOrderTraversal<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> PostOrderTraversal<'a, T> {
+        PostOrderTraversal::from_roots(tree, &[node_id])
+    }
+
+    // Visits the subtrees below the given `Node`s one after another.
+    pub(crate) fn from_roots(tree: &'a Tree<T>, root_ids: &[NodeId]) -> PostOrderTraversal<'a, T> {
+        // over allocating, but all at once instead of re-sizing and re-allocating as we go
+        let mut ids = Vec::with_capacity(tree.capacity());
+
+        for root_id in root_ids {
+            PostOrderTraversal::collect_ids(root_id, tree, &mut ids);
+        }
+
+        PostOrderTraversal {
+            tree,
+            ids: ids.into_iter(),
+        }
+    }
+
+    // Appends the `NodeId`s of the subtree below `starting_id` to `ids` in post-order without
+    // recursing.  Every frame on the stack holds a `Node` on the way down along with the index of
+    // the child to visit next, so each `Node` is looked at only once.
+    pub(crate) fn collect_ids(starting_id: &NodeId, tree: &Tree<T>, ids: &mut Vec<NodeId>) {
+        let mut stack = vec![(starting_id, 0)];
+        while let Some(frame) = stack.last_mut() {
+            let (node_id, next_child) = *frame;
+            match tree.get_unsafe(node_id).children().get(next_child) {
+                Some(child_id) => {
+                    frame.1 += 1;
+                    stack.push((child_id, 0));
+                }
+                None => {
+                    ids.push(node_id.clone());
+                    stack.pop();
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for PostOrderTraversal<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<&'a Node<T>> {
+        self.ids
+            .next()
+            .and_then(|node_id| self.tree.get(&node_id).ok())
+    }
+}
+
+impl<'a, T> Clone for PostOrderTraversal<'a, T> {
+    fn clone(&self) -> Self {
+        PostOrderTraversal {
+            tree: self.tree,
+            ids: self.ids.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the sub-tree relative to a given `Node`.
+///
+/// Iterates over all of the `NodeId`s in the sub-tree of a given `NodeId` in the `Tree`.  Each call to
+/// `next` will return the next `NodeId` in Post-Order Traversal order.
+///
+pub struct PostOrderTraversalIds<'a, T: 'a> {
+    ids: IntoIter<NodeId>,
+    phantom: PhantomData<&'a T>,
+}
+
+impl<'a, T> PostOrderTraversalIds<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> PostOrderTraversalIds<'a, T> {
+        PostOrderTraversalIds::from_roots(tree, &[node_id])
+    }
+
+    // Visits the subtrees below the given `Node`s one after another.
+    pub(crate) fn from_roots(
+        tree: &'a Tree<T>,
+        root_ids: &[NodeId],
+    ) -> PostOrderTraversalIds<'a, T> {
+        let mut ids = Vec::with_capacity(tree.capacity());
+
+        for root_id in root_ids {
+            PostOrderTraversal::collect_ids(root_id, tree, &mut ids);
+        }
+
+        PostOrderTraversalIds {
+            ids: ids.into_iter(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for PostOrderTraversalIds<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        self.ids.next()
+    }
+}
+
+impl<'a, T> Clone for PostOrderTraversalIds<'a, T> {
+    fn clone(&self) -> Self {
+        PostOrderTraversalIds {
+            ids: self.ids.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+///
+/// An Iterator over the sub-tree relative to a given `Node`.
+///
+/// Iterates over all of the `Node`s in the sub-tree of a given `Node` in the `Tree`.  Each call to
+/// `next` will return an immutable reference to the next `Node` in Level-Order Traversal order.
+///
+pub struct LevelOrderTraversal<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    data: VecDeque<NodeId>,
+}
+
+impl<'a, T> LevelOrderTraversal<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> LevelOrderTraversal<'a, T> {
+        // over allocating, but all at once instead of re-sizing and re-allocating as we go
+        let mut data = VecDeque::with_capacity(tree.capacity());
+
+        data.push_back(node_id);
+
+        LevelOrderTraversal { tree, data }
+    }
+
+    // Visits the given `Node`s first, then all of their children and so on.
+    pub(crate) fn from_roots(tree: &'a Tree<T>, root_ids: &[NodeId]) -> LevelOrderTraversal<'a, T> {
+        let mut data = VecDeque::with_capacity(tree.capacity());
+        data.extend(root_ids.iter().cloned());
+
+        LevelOrderTraversal { tree, data }
+    }
+}
+
+impl<'a, T> Iterator for LevelOrderTraversal<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<&'a Node<T>> {
+        let node_id = self.data.pop_front()?;
+        let node_ref = self.tree.get(&node_id).ok()?;
+
+        for child_id in node_ref.children() {
+            self.data.push_back(child_id.clone());
+        }
+
+        Some(node_ref)
+    }
+}
+
+impl<'a, T> Clone for LevelOrderTraversal<'a, T> {
+    fn clone(&self) -> Self {
+        LevelOrderTraversal {
+            tree: self.tree,
+            data: self.data.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the sub-tree relative to a given `Node`.
+///
+/// Iterates over all of the `NodeId`s in the sub-tree of a given `NodeId` in the `Tree`.  Each call to
+/// `next` will return the next `NodeId` in Level-Order TraversalIds order.
+///
+pub struct LevelOrderTraversalIds<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    data: VecDeque<NodeId>,
+}
+
+impl<'a, T> LevelOrderTraversalIds<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> LevelOrderTraversalIds<'a, T> {
+        // over allocating, but all at once instead of re-sizing and re-allocating as we go
+        let mut data = VecDeque::with_capacity(tree.capacity());
+
+        data.push_back(node_id);
+
+        LevelOrderTraversalIds { tree, data }
+    }
+
+    // Visits the given `Node`s first, then all of their children and so on.
+    pub(crate) fn from_roots(
+        tree: &'a Tree<T>,
+        root_ids: &[NodeId],
+    ) -> LevelOrderTraversalIds<'a, T> {
+        let mut data = VecDeque::with_capacity(tree.capacity());
+        data.extend(root_ids.iter().cloned());
+
+        LevelOrderTraversalIds { tree, data }
+    }
+}
+
+impl<'a, T> Iterator for LevelOrderTraversalIds<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        self.data.pop_front().and_then(|node_id| {
+            self.tree.get(&node_id).ok().map(|node_ref| {
+                for child_id in node_ref.children() {
+                    self.data.push_back(child_id.clone());
+                }
+
+                node_id
+            })
+        })
+    }
+}
+
+impl<'a, T> Clone for LevelOrderTraversalIds<'a, T> {
+    fn clone(&self) -> Self {
+        LevelOrderTraversalIds {
+            tree: self.tree,
+            data: self.data.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the descendants of a `Node`.
+///
+/// Iterates over the `Node`s in the sub-tree of a given `Node` in Pre-Order Traversal order,
+/// leaving out the given `Node` itself.  Each call to `next` will return an immutable reference to
+/// the next `Node`.
+///
+pub struct Descendants<'a, T: 'a> {
+    traversal: PreOrderTraversal<'a, T>,
+}
+
+impl<'a, T> Descendants<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> Descendants<'a, T> {
+        let mut traversal = PreOrderTraversal::new(tree, node_id);
+        traversal.next();
+
+        Descendants { traversal }
+    }
+}
+
+impl<'a, T> Iterator for Descendants<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<&'a Node<T>> {
+        self.traversal.next()
+    }
+}
+
+impl<'a, T> Clone for Descendants<'a, T> {
+    fn clone(&self) -> Self {
+        Descendants {
+            traversal: self.traversal.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the descendants of a `Node`.
+///
+/// Iterates over `NodeId`s instead of over the `Node`s themselves.
+///
+pub struct DescendantIds<'a, T: 'a> {
+    traversal: PreOrderTraversalIds<'a, T>,
+}
+
+impl<'a, T> DescendantIds<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> DescendantIds<'a, T> {
+        let mut traversal = PreOrderTraversalIds::new(tree, node_id);
+        traversal.next();
+
+        DescendantIds { traversal }
+    }
+}
+
+impl<'a, T> Iterator for DescendantIds<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        self.traversal.next()
+    }
+}
+
+impl<'a, T> Clone for DescendantIds<'a, T> {
+    fn clone(&self) -> Self {
+        DescendantIds {
+            traversal: self.traversal.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the leaves below a `Node`.
+///
+/// Iterates over the `Node`s without children in the sub-tree of a given `Node`, from left to
+/// right.  Each call to `next` will return an immutable reference to the next leaf `Node`.
+///
+pub struct Leaves<'a, T: 'a> {
+    traversal: PreOrderTraversal<'a, T>,
+}
+
+impl<'a, T> Leaves<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> Leaves<'a, T> {
+        Leaves {
+            traversal: PreOrderTraversal::new(tree, node_id),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Leaves<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<&'a Node<T>> {
+        self.traversal.find(|node| node.children().is_empty())
+    }
+}
+
+impl<'a, T> Clone for Leaves<'a, T> {
+    fn clone(&self) -> Self {
+        Leaves {
+            traversal: self.traversal.clone(),
+        }
+    }
+}
+
+///
+/// An Iterator over the leaves below a `Node`.
+///
+/// Iterates over `NodeId`s instead of over the `Node`s themselves.
+///
+pub struct LeafIds<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    traversal: PreOrderTraversalIds<'a, T>,
+}
+
+impl<'a, T> LeafIds<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> LeafIds<'a, T> {
+        LeafIds {
+            tree,
+            traversal: PreOrderTraversalIds::new(tree, node_id),
+        }
+    }
+}
+
+impl<'a, T> Iterator for LeafIds<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let tree = self.tree;
+        self.traversal
+            .find(|node_id| tree.get_unsafe(node_id).children().is_empty())
+    }
+}
+
+impl<'a, T> Clone for LeafIds<'a, T> {
+    fn clone(&self) -> Self {
+        LeafIds {
+            tree: self.tree,
+            traversal: self.traversal.clone(),
+        }
+    }
+}
+
+///
+/// A Pre-Order Traversal that can leave out sub-trees as it goes.
+///
+/// Iterates over all of the `NodeId`s in the sub-tree of a given `NodeId` in Pre-Order Traversal
+/// order, just like `PreOrderTraversalIds` does.  Calling `skip_subtree` after a call to `next`
+/// leaves out everything below the `Node` that was just returned, without visiting it at all.
+///
+pub struct PreOrderWalker<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    stack: Vec<NodeId>,
+    // the children of the `Node` returned last.  They are only put on the stack by the next call
+    // to `next`, so that `skip_subtree` can still drop them.
+    pending: Option<&'a [NodeId]>,
+}
+
+impl<'a, T> PreOrderWalker<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> PreOrderWalker<'a, T> {
+        PreOrderWalker {
+            tree,
+            stack: vec![node_id],
+            pending: None,
+        }
+    }
+
+    ///
+    /// Leaves out the sub-tree below the `Node` that was returned by the last call to `next`.
+    /// Does nothing if `next` hasn't been called yet.
+    ///
+    pub fn skip_subtree(&mut self) {
+        self.pending = None;
+    }
+}
+
+impl<'a, T> Iterator for PreOrderWalker<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        if let Some(child_ids) = self.pending.take() {
+            self.stack.extend(child_ids.iter().rev().cloned());
+        }
+
+        self.stack.pop().and_then(|node_id| {
+            self.tree.get(&node_id).ok().map(|node_ref| {
+                self.pending = Some(node_ref.children().as_slice());
+
+                node_id
+            })
+        })
+    }
+}
+
+impl<'a, T> Clone for PreOrderWalker<'a, T> {
+    fn clone(&self) -> Self {
+        PreOrderWalker {
+            tree: self.tree,
+            stack: self.stack.clone(),
+            pending: self.pending,
+        }
+    }
+}
+
+///
+/// An Iterator over the orphaned `Node`s of a `Tree`.
+///
+/// Iterates over the `NodeId`s of all `Node`s that have no parent but aren't the root `Node`, in
+/// the order of their slots in the `Tree`.
+///
+pub struct OrphanIds<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    slots: Enumerate<Iter<'a, Option<Node<T>>>>,
+}
+
+impl<'a, T> OrphanIds<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>) -> OrphanIds<'a, T> {
+        OrphanIds {
+            tree,
+            slots: tree.nodes.iter().enumerate(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for OrphanIds<'a, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let root_index = self.tree.root.as_ref().map(|root_id| root_id.index);
+
+        for (index, slot) in self.slots.by_ref() {
+            if let Some(ref node) = *slot {
+                if node.parent().is_none() && Some(index) != root_index {
+                    return Some(NodeId {
+                        tree_id: self.tree.id,
+                        index,
+                        generation: self.tree.generations[index],
+                    });
+                }
+            }
+        }
+        None
     }
 }
 
-impl<'a, T> Clone for PreOrderTraversal<'a, T> {
+impl<'a, T> Clone for OrphanIds<'a, T> {
     fn clone(&self) -> Self {
-        PreOrderTraversal {
-            tree: &self.tree,
-            data: self.data.clone(),
+        OrphanIds {
+            tree: self.tree,
+            slots: self.slots.clone(),
         }
     }
 }
 
 ///
-/// An Iterator over the sub-tree relative to a given `Node`.
+/// An Iterator over the roots of all subtrees in a `Tree`.
 ///
-/// Iterates over all of the `NodeIds`s in the sub-tree of a given `NodeId` in the `Tree`.  Each call to
-/// `next` will return the next `NodeId` in Pre-Order Traversal order.
+/// Iterates over the `NodeId` of the root `Node` (if there is one) followed by the `NodeId`s of
+/// all orphaned `Node`s, just like `OrphanIds` does.
 ///
-pub struct PreOrderTraversalIds<'a, T: 'a> {
-    tree: &'a Tree<T>,
-    data: VecDeque<NodeId>,
+pub struct ForestRoots<'a, T: 'a> {
+    root_id: Option<NodeId>,
+    orphan_ids: OrphanIds<'a, T>,
 }
 
-impl<'a, T> PreOrderTraversalIds<'a, T> {
-    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> PreOrderTraversalIds<'a, T> {
-        // over allocating, but all at once instead of re-sizing and re-allocating as we go
-        let mut data = VecDeque::with_capacity(tree.capacity());
-
-        data.push_front(node_id);
-
-        PreOrderTraversalIds {
-            tree: tree,
-            data: data,
+impl<'a, T> ForestRoots<'a, T> {
+    pub(crate) fn new(tree: &'a Tree<T>) -> ForestRoots<'a, T> {
+        ForestRoots {
+            root_id: tree.root.clone(),
+            orphan_ids: OrphanIds::new(tree),
         }
     }
 }
 
-impl<'a, T> Iterator for PreOrderTraversalIds<'a, T> {
+impl<'a, T> Iterator for ForestRoots<'a, T> {
     type Item = NodeId;
 
     fn next(&mut self) -> Option<NodeId> {
-        self.data.pop_front().and_then(|node_id| {
-            self.tree.get(&node_id).ok().and_then(|node_ref| {
-                // prepend child_ids
-                for child_id in node_ref.children().iter().rev() {
-                    self.data.push_front(child_id.clone());
-                }
-
-                Some(node_id)
-            })
-        })
+        self.root_id.take().or_else(|| self.orphan_ids.next())
     }
 }
 
-impl<'a, T> Clone for PreOrderTraversalIds<'a, T> {
+impl<'a, T> Clone for ForestRoots<'a, T> {
     fn clone(&self) -> Self {
-        PreOrderTraversalIds {
-            tree: &self.tree,
-            data: self.data.clone(),
+        ForestRoots {
+            root_id: self.root_id.clone(),
+            orphan_ids: self.orphan_ids.clone(),
         }
     }
 }
 
 ///
-/// An Iterator over the sub-tree relative to a given `Node`.
+/// An Iterator over the path between two `Node`s.
 ///
-/// Iterates over all of the `Node`s in the sub-tree of a given `Node` in the `Tree`.  Each call to
-/// `next` will return an immutable reference to the next `Node` in Post-Order Traversal order.
+/// Iterates over the `NodeId`s from one `Node` up to the lowest common ancestor of both `Node`s
+/// and from there down to the other `Node`.  Both ends are included.
 ///
-pub struct PostOrderTraversal<'a, T: 'a> {
+pub struct PathIds<'a, T: 'a> {
     tree: &'a Tree<T>,
-    ids: IntoIter<NodeId>,
+    up: Option<NodeId>,
+    lca: NodeId,
+    down: Vec<NodeId>,
 }
 
-impl<'a, T> PostOrderTraversal<'a, T> {
-    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> PostOrderTraversal<T> {
-        // over allocating, but all at once instead of re-sizing and re-allocating as we go
-        let mut ids = Vec::with_capacity(tree.capacity());
+impl<'a, T> PathIds<'a, T> {
+    // `down` holds the `NodeId`s below `lca` on the way to the other `Node`, deepest first.
+    pub(crate) fn new(
+        tree: &'a Tree<T>,
+        from: NodeId,
+        lca: NodeId,
+        down: Vec<NodeId>,
+    ) -> PathIds<'a, T> {
+        PathIds {
+            tree,
+            up: Some(from),
+            lca,
+            down,
+        }
+    }
+}
 
-        PostOrderTraversal::process_nodes(node_id, tree, &mut ids);
+impl<'a, T> Iterator for PathIds<'a, T> {
+    type Item = NodeId;
 
-        PostOrderTraversal {
-            tree: tree,
-            ids: ids.into_iter(),
+    fn next(&mut self) -> Option<NodeId> {
+        if let Some(current_id) = self.up.take() {
+            if current_id != self.lca {
+                self.up = self
+                    .tree
+                    .get(&current_id)
+                    .ok()
+                    .and_then(|node| node.parent())
+                    .cloned();
+            }
+            return Some(current_id);
+        }
+        self.down.pop()
+    }
+}
+
+impl<'a, T> Clone for PathIds<'a, T> {
+    fn clone(&self) -> Self {
+        PathIds {
+            tree: self.tree,
+            up: self.up.clone(),
+            lca: self.lca.clone(),
+            down: self.down.clone(),
         }
     }
+}
 
-    fn process_nodes(starting_id: NodeId, tree: &Tree<T>, ids: &mut Vec<NodeId>) {
-        let node = tree.get(&starting_id).unwrap();
+// The `*Mut` iterators below all share the same approach: the `NodeId`s to visit are collected
+// up front, while the `Tree` is still borrowed immutably, and the data is then handed out slot by
+// slot.  Every `NodeId` shows up at most once in such a traversal, so the mutable references
+// never alias.
+struct DataMut<'a, T: 'a> {
+    nodes: *mut Option<Node<T>>,
+    len: usize,
+    ids: IntoIter<NodeId>,
+    phantom: PhantomData<&'a mut Tree<T>>,
+}
 
-        for child_id in node.children() {
-            PostOrderTraversal::process_nodes(child_id.clone(), tree, ids);
+impl<'a, T> DataMut<'a, T> {
+    fn new(tree: &'a mut Tree<T>, ids: Vec<NodeId>) -> DataMut<'a, T> {
+        DataMut {
+            nodes: tree.nodes.as_mut_ptr(),
+            len: tree.nodes.len(),
+            ids: ids.into_iter(),
+            phantom: PhantomData,
         }
-
-        ids.push(starting_id);
     }
 }
 
-impl<'a, T> Iterator for PostOrderTraversal<'a, T> {
-    type Item = &'a Node<T>;
+impl<'a, T> Iterator for DataMut<'a, T> {
+    type Item = (NodeId, &'a mut T);
 
-    fn next(&mut self) -> Option<&'a Node<T>> {
-        self.ids
-            .next()
-            .and_then(|node_id| self.tree.get(&node_id).ok())
+    fn next(&mut self) -> Option<(NodeId, &'a mut T)> {
+        self.ids.next().map(|node_id| {
+            assert!(node_id.index < self.len);
+
+            // Safety: the index is in bounds, the `Tree` is mutably borrowed for `'a` and no
+            // other reference to this slot has been (or will be) handed out.
+            let slot = unsafe { &mut *self.nodes.add(node_id.index) };
+            let node = slot
+                .as_mut()
+                .expect("DataMut::next: Collected a NodeId of an empty slot.");
+
+            (node_id, &mut node.data)
+        })
     }
-}
 
-impl<'a, T> Clone for PostOrderTraversal<'a, T> {
-    fn clone(&self) -> Self {
-        PostOrderTraversal {
-            tree: &self.tree,
-            ids: self.ids.clone(),
-        }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
     }
 }
 
 ///
-/// An Iterator over the sub-tree relative to a given `Node`.
+/// An Iterator over the ancestors of a `Node`.
 ///
-/// Iterates over all of the `NodeId`s in the sub-tree of a given `NodeId` in the `Tree`.  Each call to
-/// `next` will return the next `NodeId` in Post-Order Traversal order.
+/// Iterates over the ancestor `Node`s of a given `Node` in the `Tree`.  Each call to `next` will
+/// return the `NodeId` of the next `Node` up the `Tree` along with a mutable reference to its
+/// data.
 ///
-#[derive(Clone)]
-pub struct PostOrderTraversalIds<'a, T> {
-    tree: &'a Tree<T>,
-    start_node_id: NodeId,
-    node_id: NodeId,
-    visited: HashSet<NodeId>,
+pub struct AncestorsMut<'a, T: 'a> {
+    inner: DataMut<'a, T>,
 }
 
-impl<'a, T> PostOrderTraversalIds<'a, T> {
-    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> PostOrderTraversalIds<'a, T> {
-        PostOrderTraversalIds {
-            tree,
-            start_node_id: node_id.clone(),
-            node_id,
-            visited: HashSet::new(),
+impl<'a, T> AncestorsMut<'a, T> {
+    pub(crate) fn new(tree: &'a mut Tree<T>, node_id: NodeId) -> AncestorsMut<'a, T> {
+        let ids = AncestorIds::new(tree, node_id).cloned().collect();
+        AncestorsMut {
+            inner: DataMut::new(tree, ids),
         }
     }
+}
 
-    fn unvisited_child(&self, node_id: &NodeId) -> Option<NodeId> {
-        self.tree.get(node_id).ok().and_then(|node_ref| {
-            node_ref.children().iter().find_map(|child_id| {
-                if !self.visited.contains(child_id) {
-                    Some(child_id.clone())
-                } else {
-                    None
-                }
-            })
-        })
+impl<'a, T> Iterator for AncestorsMut<'a, T> {
+    type Item = (NodeId, &'a mut T);
+
+    fn next(&mut self) -> Option<(NodeId, &'a mut T)> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
     }
 }
 
-impl<T> Iterator for PostOrderTraversalIds<'_, T> {
-    type Item = NodeId;
+///
+/// An Iterator over the children of a `Node`.
+///
+/// Iterates over the child `Node`s of a given `Node` in the `Tree`.  Each call to `next` will
+/// return the `NodeId` of the next child `Node` along with a mutable reference to its data.
+///
+pub struct ChildrenMut<'a, T: 'a> {
+    inner: DataMut<'a, T>,
+}
 
-    fn next(&mut self) -> Option<NodeId> {
-        if self.visited.contains(&self.start_node_id) {
-            // We're done.
-            return None;
+impl<'a, T> ChildrenMut<'a, T> {
+    pub(crate) fn new(tree: &'a mut Tree<T>, node_id: NodeId) -> ChildrenMut<'a, T> {
+        let ids = tree.get_unsafe(&node_id).children().clone();
+        ChildrenMut {
+            inner: DataMut::new(tree, ids),
         }
+    }
+}
 
-        // Move down the leftmost unvisited path
-        while let Some(node_id) = self.unvisited_child(&self.node_id) {
-            self.node_id = node_id
-        }
-        let most_down_node_id = self.node_id.clone();
+impl<'a, T> Iterator for ChildrenMut<'a, T> {
+    type Item = (NodeId, &'a mut T);
 
-        if let Some(parent_id) = self
-            .tree
-            .get(&most_down_node_id)
-            .ok()
-            .and_then(|node_ref| node_ref.parent())
-        {
-            // Move up for next iteration
-            self.node_id = parent_id.clone();
-        }
+    fn next(&mut self) -> Option<(NodeId, &'a mut T)> {
+        self.inner.next()
+    }
 
-        // No unvisited children - visit this node
-        self.visited.insert(most_down_node_id.clone());
-        Some(most_down_node_id)
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
     }
 }
 
@@ -395,108 +1174,105 @@ impl<T> Iterator for PostOrderTraversalIds<'_, T> {
 /// An Iterator over the sub-tree relative to a given `Node`.
 ///
 /// Iterates over all of the `Node`s in the sub-tree of a given `Node` in the `Tree`.  Each call to
-/// `next` will return an immutable reference to the next `Node` in Level-Order Traversal order.
+/// `next` will return the `NodeId` of the next `Node` in Pre-Order Traversal order along with a
+/// mutable reference to its data.
 ///
-pub struct LevelOrderTraversal<'a, T: 'a> {
-    tree: &'a Tree<T>,
-    data: VecDeque<NodeId>,
+pub struct PreOrderTraversalMut<'a, T: 'a> {
+    inner: DataMut<'a, T>,
 }
 
-impl<'a, T> LevelOrderTraversal<'a, T> {
-    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> LevelOrderTraversal<T> {
-        // over allocating, but all at once instead of re-sizing and re-allocating as we go
-        let mut data = VecDeque::with_capacity(tree.capacity());
-
-        data.push_back(node_id);
-
-        LevelOrderTraversal {
-            tree: tree,
-            data: data,
+impl<'a, T> PreOrderTraversalMut<'a, T> {
+    pub(crate) fn new(tree: &'a mut Tree<T>, node_id: NodeId) -> PreOrderTraversalMut<'a, T> {
+        let ids = PreOrderTraversalIds::new(tree, node_id).collect();
+        PreOrderTraversalMut {
+            inner: DataMut::new(tree, ids),
         }
     }
 }
 
-impl<'a, T> Iterator for LevelOrderTraversal<'a, T> {
-    type Item = &'a Node<T>;
-
-    fn next(&mut self) -> Option<&'a Node<T>> {
-        self.data
-            .pop_front()
-            .and_then(|node_id| self.tree.get(&node_id).ok())
-            .and_then(|node_ref| {
-                for child_id in node_ref.children() {
-                    self.data.push_back(child_id.clone());
-                }
+impl<'a, T> Iterator for PreOrderTraversalMut<'a, T> {
+    type Item = (NodeId, &'a mut T);
 
-                Some(node_ref)
-            })
+    fn next(&mut self) -> Option<(NodeId, &'a mut T)> {
+        self.inner.next()
     }
-}
 
-impl<'a, T> Clone for LevelOrderTraversal<'a, T> {
-    fn clone(&self) -> Self {
-        LevelOrderTraversal {
-            tree: &self.tree,
-            data: self.data.clone(),
-        }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
     }
 }
 
 ///
 /// An Iterator over the sub-tree relative to a given `Node`.
 ///
-/// Iterates over all of the `NodeId`s in the sub-tree of a given `NodeId` in the `Tree`.  Each call to
-/// `next` will return the next `NodeId` in Level-Order TraversalIds order.
+/// Iterates over all of the `Node`s in the sub-tree of a given `Node` in the `Tree`.  Each call to
+/// `next` will return the `NodeId` of the next `Node` in Post-Order Traversal order along with a
+/// mutable reference to its data.
 ///
-pub struct LevelOrderTraversalIds<'a, T: 'a> {
-    tree: &'a Tree<T>,
-    data: VecDeque<NodeId>,
+pub struct PostOrderTraversalMut<'a, T: 'a> {
+    inner: DataMut<'a, T>,
 }
 
-impl<'a, T> LevelOrderTraversalIds<'a, T> {
-    pub(crate) fn new(tree: &'a Tree<T>, node_id: NodeId) -> LevelOrderTraversalIds<T> {
-        // over allocating, but all at once instead of re-sizing and re-allocating as we go
-        let mut data = VecDeque::with_capacity(tree.capacity());
-
-        data.push_back(node_id);
-
-        LevelOrderTraversalIds {
-            tree: tree,
-            data: data,
+impl<'a, T> PostOrderTraversalMut<'a, T> {
+    pub(crate) fn new(tree: &'a mut Tree<T>, node_id: NodeId) -> PostOrderTraversalMut<'a, T> {
+        let mut ids = Vec::new();
+        PostOrderTraversal::collect_ids(&node_id, tree, &mut ids);
+        PostOrderTraversalMut {
+            inner: DataMut::new(tree, ids),
         }
     }
 }
 
-impl<'a, T> Iterator for LevelOrderTraversalIds<'a, T> {
-    type Item = NodeId;
+impl<'a, T> Iterator for PostOrderTraversalMut<'a, T> {
+    type Item = (NodeId, &'a mut T);
 
-    fn next(&mut self) -> Option<NodeId> {
-        self.data.pop_front().and_then(|node_id| {
-            self.tree.get(&node_id).ok().and_then(|node_ref| {
-                for child_id in node_ref.children() {
-                    self.data.push_back(child_id.clone());
-                }
+    fn next(&mut self) -> Option<(NodeId, &'a mut T)> {
+        self.inner.next()
+    }
 
-                Some(node_id)
-            })
-        })
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
     }
 }
 
-impl<'a, T> Clone for LevelOrderTraversalIds<'a, T> {
-    fn clone(&self) -> Self {
-        LevelOrderTraversalIds {
-            tree: &self.tree,
-            data: self.data.clone(),
+///
+/// An Iterator over the sub-tree relative to a given `Node`.
+///
+/// Iterates over all of the `Node`s in the sub-tree of a given `Node` in the `Tree`.  Each call to
+/// `next` will return the `NodeId` of the next `Node` in Level-Order Traversal order along with a
+/// mutable reference to its data.
+///
+pub struct LevelOrderTraversalMut<'a, T: 'a> {
+    inner: DataMut<'a, T>,
+}
+
+impl<'a, T> LevelOrderTraversalMut<'a, T> {
+    pub(crate) fn new(tree: &'a mut Tree<T>, node_id: NodeId) -> LevelOrderTraversalMut<'a, T> {
+        let ids = LevelOrderTraversalIds::new(tree, node_id).collect();
+        LevelOrderTraversalMut {
+            inner: DataMut::new(tree, ids),
         }
     }
 }
 
+impl<'a, T> Iterator for LevelOrderTraversalMut<'a, T> {
+    type Item = (NodeId, &'a mut T);
+
+    fn next(&mut self) -> Option<(NodeId, &'a mut T)> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use InsertBehavior::*;
     use Node;
+    use NodeId;
     use Tree;
 
     #[test]
@@ -726,6 +1502,58 @@ mod tests {
         assert_eq!(root_children_ids_clone.next(), Some(&node_2));
     }
 
+    #[test]
+    fn test_siblings() {
+        let mut tree = Tree::new();
+
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+
+        let data = |siblings: Vec<&Node<i32>>| -> Vec<i32> {
+            siblings.into_iter().map(|node| *node.data()).collect()
+        };
+
+        assert_eq!(data(tree.siblings(&node_2).unwrap().collect()), vec![1, 3]);
+        assert_eq!(data(tree.siblings(&node_1).unwrap().collect()), vec![2, 3]);
+        assert_eq!(tree.siblings(&root_id).unwrap().count(), 0);
+
+        let following = tree.following_siblings(&node_1).unwrap();
+        assert_eq!(data(following.clone().collect()), vec![2, 3]);
+        assert_eq!(data(following.skip(1).collect()), vec![3]);
+
+        let preceding = tree.preceding_siblings(&node_2).unwrap();
+        assert_eq!(data(preceding.clone().collect()), vec![1]);
+        assert_eq!(preceding.skip(1).count(), 0);
+        assert_eq!(tree.preceding_siblings(&node_1).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_sibling_ids() {
+        let mut tree = Tree::new();
+
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+
+        let sibling_ids: Vec<&NodeId> = tree.sibling_ids(&node_3).unwrap().collect();
+        assert_eq!(sibling_ids, vec![&node_1, &node_2]);
+        assert_eq!(tree.sibling_ids(&root_id).unwrap().count(), 0);
+
+        let following_ids: Vec<&NodeId> = tree.following_sibling_ids(&node_2).unwrap().collect();
+        assert_eq!(following_ids, vec![&node_3]);
+        assert_eq!(tree.following_sibling_ids(&node_3).unwrap().count(), 0);
+
+        let preceding_ids = tree.preceding_sibling_ids(&node_3).unwrap();
+        assert_eq!(
+            preceding_ids.clone().collect::<Vec<_>>(),
+            vec![&node_2, &node_1]
+        );
+        assert_eq!(preceding_ids.skip(2).count(), 0);
+    }
+
     #[test]
     fn test_pre_order_traversal() {
         let mut tree = Tree::new();
@@ -870,6 +1698,96 @@ mod tests {
         assert_eq!(traversal_from_root_ids_clone.next(), Some(node_1));
     }
 
+    #[test]
+    fn test_descendants_and_leaves() {
+        let mut tree = Tree::new();
+
+        //      0
+        //     / \
+        //    1   2
+        //   / \
+        //  3   4
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let node_3 = tree.insert(Node::new(3), UnderNode(&node_1)).unwrap();
+        let node_4 = tree.insert(Node::new(4), UnderNode(&node_1)).unwrap();
+
+        let data: Vec<i32> = tree
+            .descendants(&root_id)
+            .unwrap()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(data, vec![1, 3, 4, 2]);
+        assert_eq!(tree.descendants(&node_2).unwrap().count(), 0);
+
+        let mut descendant_ids = tree.descendant_ids(&node_1).unwrap();
+        assert_eq!(
+            descendant_ids.clone().collect::<Vec<_>>(),
+            vec![node_3.clone(), node_4.clone()]
+        );
+        assert_eq!(descendant_ids.nth(1), Some(node_4.clone()));
+
+        let mut leaves = tree.leaves(&root_id).unwrap();
+        let data: Vec<i32> = leaves.clone().map(|node| *node.data()).collect();
+        assert_eq!(data, vec![3, 4, 2]);
+        assert_eq!(leaves.nth(2).unwrap().data(), &2);
+
+        // a leaf is the only leaf below itself
+        let leaf_ids: Vec<NodeId> = tree.leaf_ids(&node_2).unwrap().collect();
+        assert_eq!(leaf_ids, vec![node_2.clone()]);
+        let leaf_ids: Vec<NodeId> = tree.leaf_ids(&root_id).unwrap().collect();
+        assert_eq!(leaf_ids, vec![node_3, node_4, node_2]);
+    }
+
+    #[test]
+    fn test_pre_order_walker() {
+        let mut tree = Tree::new();
+
+        //      0
+        //     / \
+        //    1   2
+        //   /   / \
+        //  3   4   5
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(3), UnderNode(&node_1)).unwrap();
+        tree.insert(Node::new(4), UnderNode(&node_2)).unwrap();
+        tree.insert(Node::new(5), UnderNode(&node_2)).unwrap();
+
+        let walk = |skip: &[i32]| -> Vec<i32> {
+            let mut walker = tree.walk_pre_order(&root_id).unwrap();
+            let mut data = Vec::new();
+            while let Some(node_id) = walker.next() {
+                let value = *tree.get(&node_id).unwrap().data();
+                if skip.contains(&value) {
+                    walker.skip_subtree();
+                }
+                data.push(value);
+            }
+            data
+        };
+
+        assert_eq!(walk(&[]), vec![0, 1, 3, 2, 4, 5]);
+        assert_eq!(walk(&[1]), vec![0, 1, 2, 4, 5]);
+        assert_eq!(walk(&[2]), vec![0, 1, 3, 2]);
+        assert_eq!(walk(&[1, 2]), vec![0, 1, 2]);
+        assert_eq!(walk(&[0]), vec![0]);
+        assert_eq!(walk(&[3, 4]), vec![0, 1, 3, 2, 4, 5]);
+
+        // skipping before the first call to `next` changes nothing
+        let mut walker = tree.walk_pre_order(&node_2).unwrap();
+        walker.skip_subtree();
+        assert_eq!(walker.next(), Some(node_2));
+
+        // the clone keeps whether the sub-tree is going to be visited
+        walker.skip_subtree();
+        let walker_clone = walker.clone();
+        assert_eq!(walker.next(), None);
+        assert_eq!(walker_clone.count(), 0);
+    }
+
     #[test]
     fn test_post_order_traversal() {
         let mut tree = Tree::new();
@@ -1155,4 +2073,119 @@ mod tests {
 
         assert_eq!(traversal_from_root_ids_clone.next(), Some(node_1));
     }
+
+    #[test]
+    fn test_ancestors_mut() {
+        let mut tree = Tree::new();
+
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2 = tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+
+        for (_, data) in tree.ancestors_mut(&node_2).unwrap() {
+            *data += 10;
+        }
+
+        let ids: Vec<_> = tree
+            .ancestors_mut(&node_2)
+            .unwrap()
+            .map(|(node_id, _)| node_id)
+            .collect();
+        assert_eq!(ids, vec![node_1.clone(), root_id.clone()]);
+
+        assert_eq!(tree.get(&root_id).unwrap().data(), &10);
+        assert_eq!(tree.get(&node_1).unwrap().data(), &11);
+        assert_eq!(tree.get(&node_2).unwrap().data(), &2);
+    }
+
+    #[test]
+    fn test_children_mut() {
+        let mut tree = Tree::new();
+
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let node_3 = tree.insert(Node::new(3), UnderNode(&node_1)).unwrap();
+
+        let children = tree.children_mut(&root_id).unwrap();
+        assert_eq!(children.size_hint(), (2, Some(2)));
+        for (_, data) in children {
+            *data *= 10;
+        }
+
+        assert_eq!(tree.get(&root_id).unwrap().data(), &0);
+        assert_eq!(tree.get(&node_1).unwrap().data(), &10);
+        assert_eq!(tree.get(&node_2).unwrap().data(), &20);
+        assert_eq!(tree.get(&node_3).unwrap().data(), &3);
+    }
+
+    #[test]
+    // quadratic when every insertion checks the whole Tree
+    #[cfg(not(all(feature = "integrity_checks", debug_assertions)))]
+    fn test_post_order_mut_wide() {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        for i in 1..100_000 {
+            tree.insert(Node::new(i), UnderNode(&root_id)).unwrap();
+        }
+
+        let data: Vec<i32> = tree
+            .traverse_post_order_mut(&root_id)
+            .unwrap()
+            .map(|(_, data)| *data)
+            .collect();
+        let mut expected: Vec<i32> = (1..100_000).collect();
+        expected.push(0);
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_traversals_mut() {
+        let mut tree = Tree::new();
+
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(3), UnderNode(&node_1)).unwrap();
+
+        for (count, (_, data)) in tree.traverse_pre_order_mut(&root_id).unwrap().enumerate() {
+            *data = *data * 10 + count as i32;
+        }
+        let data: Vec<i32> = tree
+            .traverse_pre_order(&root_id)
+            .unwrap()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(data, vec![0, 11, 32, 23]);
+
+        let ids: Vec<_> = tree
+            .traverse_post_order_mut(&root_id)
+            .unwrap()
+            .map(|(node_id, data)| {
+                *data = 0;
+                node_id
+            })
+            .collect();
+        let expected: Vec<_> = tree.traverse_post_order_ids(&root_id).unwrap().collect();
+        assert_eq!(ids, expected);
+        assert!(tree
+            .traverse_pre_order(&root_id)
+            .unwrap()
+            .all(|node| *node.data() == 0));
+
+        // all references can be held at the same time
+        let mut refs: Vec<&mut i32> = tree
+            .traverse_level_order_mut(&node_1)
+            .unwrap()
+            .map(|(_, data)| data)
+            .collect();
+        *refs[0] = 1;
+        *refs[1] = 3;
+        let data: Vec<i32> = tree
+            .traverse_level_order(&root_id)
+            .unwrap()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(data, vec![0, 1, 0, 3]);
+    }
 }
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use Node;
+use NodeId;
+use Tree;
+
+// The state of a single slot of a `Tree`.  `links` is `None` if the slot was empty.
+struct SlotState {
+    generation: usize,
+    links: Option<(Option<NodeId>, Vec<NodeId>)>,
+}
+
+impl SlotState {
+    fn of<T>(nodes: &[Option<Node<T>>], generations: &[usize], index: usize) -> SlotState {
+        if index < nodes.len() {
+            SlotState {
+                generation: generations[index],
+                links: nodes[index]
+                    .as_ref()
+                    .map(|node| (node.parent.clone(), node.children.clone())),
+            }
+        } else {
+            SlotState {
+                generation: 0,
+                links: None,
+            }
+        }
+    }
+}
+
+// Everything needed to bring a `Tree` back into the state it was in before a mutation.
+//
+// Only the slots that were touched are recorded.  For slots that hold a `Node` both before and
+// after, only the links are restored so that the data stays as it is; `data` holds the data of
+// slots that have to be filled again.
+//
+// The free ids are a stack and a mutation only pops from or pushes onto it, so everything below
+// `free_keep` is left as it was and only the part above it has to be recorded.
+struct Entry<T> {
+    root: Option<NodeId>,
+    nodes_len: usize,
+    free_keep: usize,
+    free_tail: Vec<NodeId>,
+    slots: HashMap<usize, SlotState>,
+    data: HashMap<usize, T>,
+}
+
+impl<T> Entry<T> {
+    // Restores the state recorded in this `Entry` and returns an `Entry` that can be used to
+    // get back to the state the `Tree` was in before.
+    fn apply(self, tree: &mut Tree<T>) -> Entry<T> {
+        let mut inverse = Entry {
+            root: tree.root.take(),
+            nodes_len: tree.nodes.len(),
+            free_keep: self.free_keep,
+            free_tail: tree.free_ids.split_off(self.free_keep),
+            slots: HashMap::with_capacity(self.slots.len()),
+            data: HashMap::new(),
+        };
+
+        tree.root = self.root;
+        tree.free_ids.extend(self.free_tail);
+
+        if tree.nodes.len() < self.nodes_len {
+            tree.nodes.resize_with(self.nodes_len, || None);
+            tree.generations.resize(self.nodes_len, 0);
+        }
+
+        let mut data = self.data;
+        for (index, state) in self.slots {
+            let current = SlotState::of(&tree.nodes, &tree.generations, index);
+            // the generation may go back, but a `Node` inserted later on must not get a `NodeId`
+            // that was handed out already
+            let handed_out = current.generation + current.links.is_some() as usize;
+            tree.raise_min_generation(index, handed_out);
+            inverse.slots.insert(index, current);
+            tree.generations[index] = state.generation;
+
+            match (state.links, tree.nodes[index].take()) {
+                (Some((parent, children)), Some(mut node)) => {
+                    node.parent = parent;
+                    node.children = children;
+                    tree.nodes[index] = Some(node);
+                }
+                (Some((parent, children)), None) => {
+                    let data = data
+                        .remove(&index)
+                        .expect("Entry::apply: Missing the data of a removed Node.");
+                    tree.nodes[index] = Some(Node {
+                        data,
+                        parent,
+                        children,
+                    });
+                }
+                (None, Some(node)) => {
+                    inverse.data.insert(index, node.data);
+                }
+                (None, None) => {}
+            }
+        }
+
+        tree.nodes.truncate(self.nodes_len);
+        tree.generations.truncate(self.nodes_len);
+
+        inverse
+    }
+}
+
+pub(crate) struct Journal<T> {
+    clone_data: fn(&T) -> T,
+    undo: Vec<Entry<T>>,
+    redo: Vec<Entry<T>>,
+    current: Option<Entry<T>>,
+    depth: usize,
+}
+
+impl<T> Journal<T> {
+    pub(crate) fn new(clone_data: fn(&T) -> T) -> Journal<T> {
+        Journal {
+            clone_data,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            current: None,
+            depth: 0,
+        }
+    }
+
+    pub(crate) fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub(crate) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    // Mutations can be nested (one public method calling another), only the outermost one gets
+    // its own entry.
+    pub(crate) fn begin(&mut self, root: Option<NodeId>, nodes_len: usize, free_len: usize) {
+        if self.depth == 0 {
+            self.current = Some(Entry {
+                root,
+                nodes_len,
+                free_keep: free_len,
+                free_tail: Vec::new(),
+                slots: HashMap::new(),
+                data: HashMap::new(),
+            });
+        }
+        self.depth += 1;
+    }
+
+    pub(crate) fn end(&mut self) {
+        self.depth -= 1;
+        if self.depth == 0 {
+            if let Some(entry) = self.current.take() {
+                if !entry.slots.is_empty() {
+                    self.undo.push(entry);
+                    self.redo.clear();
+                }
+            }
+        }
+    }
+
+    // Records the state of a slot the first time it is about to be changed.
+    pub(crate) fn record_slot(
+        &mut self,
+        nodes: &[Option<Node<T>>],
+        generations: &[usize],
+        index: usize,
+    ) {
+        if let Some(ref mut entry) = self.current {
+            entry
+                .slots
+                .entry(index)
+                .or_insert_with(|| SlotState::of(nodes, generations, index));
+        }
+    }
+
+    // Keeps a copy of the data of a `Node` that is being taken out of its slot.
+    pub(crate) fn record_taken(&mut self, index: usize, data: &T) {
+        let clone_data = self.clone_data;
+        if let Some(ref mut entry) = self.current {
+            let was_occupied = entry
+                .slots
+                .get(&index)
+                .is_some_and(|state| state.links.is_some());
+            if was_occupied && !entry.data.contains_key(&index) {
+                entry.data.insert(index, clone_data(data));
+            }
+        }
+    }
+
+    // Records that the free id at position `position` is being popped.
+    pub(crate) fn record_free_pop(&mut self, position: usize, free_id: &NodeId) {
+        if let Some(ref mut entry) = self.current {
+            if position < entry.free_keep {
+                entry.free_tail.insert(0, free_id.clone());
+                entry.free_keep = position;
+            }
+        }
+    }
+
+    pub(crate) fn undo(&mut self, tree: &mut Tree<T>) -> bool {
+        match self.undo.pop() {
+            Some(entry) => {
+                let inverse = entry.apply(tree);
+                self.redo.push(inverse);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn redo(&mut self, tree: &mut Tree<T>) -> bool {
+        match self.redo.pop() {
+            Some(entry) => {
+                let inverse = entry.apply(tree);
+                self.undo.push(inverse);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Journal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Journal")
+            .field("undo", &self.undo.len())
+            .field("redo", &self.redo.len())
+            .finish()
+    }
+}
@@ -75,29 +75,76 @@ extern crate serde_derive;
 extern crate snowflake;
 use self::snowflake::ProcessUniqueId;
 
+mod ancestor_index;
 mod behaviors;
+mod dot;
 mod error;
+mod event;
+mod forest;
+mod formatter;
+mod integrity;
 mod iterators;
+mod journal;
+#[cfg(feature = "serde_support")]
+pub mod nested;
 mod node;
+#[cfg(feature = "serde_support")]
+mod node_id_seed;
+mod parser;
+mod persistent;
 mod tree;
 
+pub use ancestor_index::AncestorIndex;
 pub use behaviors::InsertBehavior;
 pub use behaviors::MoveBehavior;
 pub use behaviors::RemoveBehavior;
 pub use behaviors::SwapBehavior;
+pub use dot::DotOptions;
+pub use error::AncestorIndexError;
+pub use error::FormatError;
+pub use error::GraftError;
+pub use error::IntegrityError;
 pub use error::NodeIdError;
+pub use error::ParseError;
+pub use error::ParseErrorKind;
+pub use event::TreeEvent;
+pub use forest::Forest;
+pub use formatter::TreeFormatter;
 pub use iterators::AncestorIds;
 pub use iterators::Ancestors;
+pub use iterators::AncestorsMut;
 pub use iterators::Children;
 pub use iterators::ChildrenIds;
+pub use iterators::ChildrenMut;
+pub use iterators::DescendantIds;
+pub use iterators::Descendants;
+pub use iterators::FollowingSiblingIds;
+pub use iterators::FollowingSiblings;
+pub use iterators::ForestRoots;
+pub use iterators::LeafIds;
+pub use iterators::Leaves;
 pub use iterators::LevelOrderTraversal;
 pub use iterators::LevelOrderTraversalIds;
+pub use iterators::LevelOrderTraversalMut;
+pub use iterators::OrphanIds;
+pub use iterators::PathIds;
 pub use iterators::PostOrderTraversal;
 pub use iterators::PostOrderTraversalIds;
+pub use iterators::PostOrderTraversalMut;
 pub use iterators::PreOrderTraversal;
 pub use iterators::PreOrderTraversalIds;
+pub use iterators::PreOrderTraversalMut;
+pub use iterators::PreOrderWalker;
+pub use iterators::PrecedingSiblingIds;
+pub use iterators::PrecedingSiblings;
+pub use iterators::SiblingIds;
+pub use iterators::Siblings;
 pub use node::Node;
 pub use node::NodeBuilder;
+#[cfg(feature = "serde_support")]
+pub use node_id_seed::NodeIdSeed;
+pub use persistent::PersistentNode;
+pub use persistent::PersistentTree;
 pub use tree::Tree;
 pub use tree::TreeBuilder;
 
@@ -117,39 +164,39 @@ pub use tree::TreeBuilder;
 /// Because `Tree`s pass out `NodeId`s as `Node`s are inserted, several issues can occur:
 ///
 /// 1. If a `Node` is removed, the `NodeId` that previously identified it now points to nothing
-/// (technically a `None` value in this case).
-/// 2. If a `Node` is removed and then another is inserted later, the "new" `NodeId` that is
-/// returned can (and will) be the same `NodeId` that was used to identify a different `Node`
-/// previously.
+///    (technically a `None` value in this case).
+/// 2. If a `Node` is removed and then another is inserted later, the "new" `Node` can (and will)
+///    be stored in the same place that was used to store a different `Node` previously.
 ///
 /// The above issues may seem like deal-breakers, but our situation isn't as bad as it seems:
 ///
 /// The first issue can be easily detected by the library itself.  In this situation, a
-/// `Result::Err` will be returned with the appropriate `NodeIdError`. The second issue, however, is
-/// not something that the library can detect. To mitigate this problem, this library ensures the
-/// following:
+/// `Result::Err` will be returned with the appropriate `NodeIdError`.  The second issue is
+/// detected as well: every storage slot in a `Tree` carries a generation counter that is bumped
+/// whenever a `Node` is removed from it, and every `NodeId` remembers the generation it was
+/// handed out with.  An old `NodeId` will therefore never point at a `Node` that was inserted
+/// after its own `Node` was removed; `NodeIdError::NodeIdNoLongerValid` is returned instead.
+///
+/// In addition, this library ensures the following:
 ///
 /// 1. All `Node` methods that provide `NodeId`s will **return** `&NodeId`s instead of `NodeId`s.
 /// 2. All `Tree` methods that **read** or **insert** data accept `&NodeId`s instead of taking
-/// `NodeId`s.
+///    `NodeId`s.
 /// 3. All `Tree` methods that **remove** data take `NodeId`s instead of accepting `&NodeId`s.
 /// 4. All `Node`s that have been removed from a `Tree` will have their parent and child references
-/// cleared (to avoid leaking extra `NodeId` copies).
+///    cleared (to avoid leaking extra `NodeId` copies).
 /// 5. `NodeId`s themselves are `Clone`, but not `Copy`.
 ///
 /// This means that no methods will ever take ownership of a `NodeId` except for methods that remove
 /// a `Node` from a `Tree`. The resulting behavior is that unless the caller **explicitly `Clone`s a
 /// `NodeId`** they should never be in a situation where they accidentally hold onto a `NodeId` too
-/// long.  This means that we have "almost safe references" that the caller can clone if they choose
-/// to.  Doing so, however, will open up the possibility of confusing which `NodeId`s refer to which
-/// `Node`s in the calling context.
-///
-/// This _does_ transfer some of the burden to the caller, but any errors should be fairly easy to
-/// sort out because an explicit `Clone` is required for such an error to occur.
+/// long.  Should they do so anyway, the `Tree` will reject that `NodeId` rather than returning the
+/// wrong `Node`.
 ///
 #[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
-#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_support", derive(Serialize))]
 pub struct NodeId {
     tree_id: ProcessUniqueId,
     index: usize,
+    generation: usize,
 }
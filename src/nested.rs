@@ -0,0 +1,255 @@
+//!
+//! A nested representation of a `Tree` for serde, to be used with
+//! `#[serde(with = "id_tree::nested")]`.
+//!
+//! Instead of the internals of the `Tree`, every `Node` is written as an object holding its data
+//! and a list of its children, starting at the root `Node`:
+//!
+//! ```json
+//! {"data": 0, "children": [{"data": 1, "children": []}, {"data": 2, "children": []}]}
+//! ```
+//!
+//! An empty `Tree` is written as `null` (or whatever the format uses for `None`).  `children`
+//! can be left out when reading a `Node` that has none.  Reading always creates a fresh `Tree`
+//! with the `Node`s laid out in pre-order, so `NodeId`s of the `Tree` that was written can't be
+//! used on it.
+//!
+//! Only the `Node`s that can be reached from the root `Node` are written.  Since every `Node` is
+//! nested inside its parent, writing a `Tree` takes stack space for every level of it, so `Tree`s
+//! that are more than `MAX_DEPTH` levels deep are refused with an error instead of overflowing
+//! the stack.  Most formats limit the nesting they read as well: `serde_json`, for example, only
+//! reads `Tree`s up to 63 levels deep unless its recursion limit is turned off.
+//!
+//! ```
+//! extern crate id_tree;
+//! #[macro_use]
+//! extern crate serde_derive;
+//! extern crate serde_json;
+//!
+//! use id_tree::*;
+//! use id_tree::InsertBehavior::*;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Document {
+//!     #[serde(with = "id_tree::nested")]
+//!     tree: Tree<i32>,
+//! }
+//!
+//! fn main() {
+//!     let mut tree: Tree<i32> = Tree::new();
+//!     let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+//!     tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+//!
+//!     let json = serde_json::to_string(&Document { tree }).unwrap();
+//!     assert_eq!(json, r#"{"tree":{"data":0,"children":[{"data":1,"children":[]}]}}"#);
+//!
+//!     let json = r#"{"tree": {"data": 2, "children": [{"data": 3}]}}"#;
+//!     let document: Document = serde_json::from_str(json).unwrap();
+//!
+//!     let root_id = document.tree.root_node_id().unwrap();
+//!     let child = document.tree.children(root_id).unwrap().next().unwrap();
+//!     assert_eq!(child.data(), &3);
+//! }
+//! ```
+//!
+
+use serde::ser::{Error, SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use InsertBehavior;
+use Node;
+use NodeId;
+use Tree;
+
+///
+/// The height of the deepest `Tree` that `serialize` writes.
+///
+pub const MAX_DEPTH: usize = 1000;
+
+///
+/// Writes the `Tree` in the nested representation.
+///
+/// Returns an error if the `Tree` is more than `MAX_DEPTH` levels deep.
+///
+pub fn serialize<T, S>(tree: &Tree<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    if tree.height() > MAX_DEPTH {
+        return Err(S::Error::custom(format_args!(
+            "the Tree is more than {} levels deep",
+            MAX_DEPTH
+        )));
+    }
+
+    let root = tree.root_node_id().map(|root_id| NestedNode {
+        tree,
+        node_id: root_id,
+    });
+    root.serialize(serializer)
+}
+
+///
+/// Reads a `Tree` from the nested representation.
+///
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Tree<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let mut tree = Tree::new();
+
+    let root = match Option::<OwnedNode<T>>::deserialize(deserializer)? {
+        Some(root) => root,
+        None => return Ok(tree),
+    };
+
+    // insert in pre-order so that the `Node`s end up in consecutive slots
+    let mut stack: Vec<(OwnedNode<T>, Option<NodeId>)> = vec![(root, None)];
+    while let Some((node, parent_id)) = stack.pop() {
+        let behavior = match parent_id {
+            Some(ref parent_id) => InsertBehavior::UnderNode(parent_id),
+            None => InsertBehavior::AsRoot,
+        };
+        let node_id = tree
+            .insert(Node::new(node.data), behavior)
+            .expect("nested::deserialize: The parent of a new Node is always in the Tree.");
+
+        for child in node.children.into_iter().rev() {
+            stack.push((child, Some(node_id.clone())));
+        }
+    }
+
+    Ok(tree)
+}
+
+struct NestedNode<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    node_id: &'a NodeId,
+}
+
+impl<'a, T: Serialize> Serialize for NestedNode<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let node = self.tree.get_unsafe(self.node_id);
+
+        let mut state = serializer.serialize_struct("Node", 2)?;
+        state.serialize_field("data", node.data())?;
+        state.serialize_field(
+            "children",
+            &NestedChildren {
+                tree: self.tree,
+                node_ids: node.children(),
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct NestedChildren<'a, T: 'a> {
+    tree: &'a Tree<T>,
+    node_ids: &'a [NodeId],
+}
+
+impl<'a, T: Serialize> Serialize for NestedChildren<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.node_ids.len()))?;
+        for node_id in self.node_ids {
+            seq.serialize_element(&NestedNode {
+                tree: self.tree,
+                node_id,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Node")]
+struct OwnedNode<T> {
+    data: T,
+    #[serde(default = "Vec::new")]
+    children: Vec<OwnedNode<T>>,
+}
+
+#[cfg(test)]
+mod tests {
+
+    extern crate serde_json;
+
+    use InsertBehavior::*;
+    use Node;
+    use RemoveBehavior::*;
+    use Tree;
+
+    fn to_json(tree: &Tree<i32>) -> String {
+        let mut buffer = Vec::new();
+        super::serialize(tree, &mut serde_json::Serializer::new(&mut buffer)).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn from_json(json: &str) -> Tree<i32> {
+        super::deserialize(&mut serde_json::Deserializer::from_str(json)).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let first_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let second_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(3), UnderNode(&second_id)).unwrap();
+        tree.insert(Node::new(4), UnderNode(&second_id)).unwrap();
+        tree.remove_node(first_id, DropChildren).unwrap();
+
+        let json = to_json(&tree);
+        assert_eq!(
+            json,
+            r#"{"data":0,"children":[{"data":2,"children":[{"data":3,"children":[]},{"data":4,"children":[]}]}]}"#
+        );
+
+        // the hole left by the removed `Node` is gone
+        let read = from_json(&json);
+        assert_eq!(read.nodes.len(), 4);
+        assert!(read.free_ids.is_empty());
+
+        let data: Vec<i32> = read
+            .traverse_pre_order(read.root_node_id().unwrap())
+            .unwrap()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(data, vec![0, 2, 3, 4]);
+        assert_eq!(to_json(&read), json);
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        assert_eq!(to_json(&Tree::new()), "null");
+        assert!(from_json("null").root_node_id().is_none());
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let mut tree = Tree::new();
+        let mut node_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        for data in 1..super::MAX_DEPTH as i32 {
+            node_id = tree.insert(Node::new(data), UnderNode(&node_id)).unwrap();
+        }
+
+        let json = to_json(&tree);
+        assert_eq!(json.matches("\"data\"").count(), super::MAX_DEPTH);
+
+        tree.insert(Node::new(0), UnderNode(&node_id)).unwrap();
+        let mut buffer = Vec::new();
+        let result = super::serialize(&tree, &mut serde_json::Serializer::new(&mut buffer));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let result: Result<Tree<i32>, _> = super::deserialize(
+            &mut serde_json::Deserializer::from_str(r#"{"children": []}"#),
+        );
+        assert!(result.is_err());
+    }
+}
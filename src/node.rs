@@ -20,7 +20,7 @@ impl<T> NodeBuilder<T> {
     ///
     pub fn new(data: T) -> NodeBuilder<T> {
         NodeBuilder {
-            data: data,
+            data,
             child_capacity: 0,
         }
     }
@@ -204,6 +204,10 @@ impl<T> Node<T> {
         self.children.push(child);
     }
 
+    pub(crate) fn insert_child(&mut self, index: usize, child: NodeId) {
+        self.children.insert(index, child);
+    }
+
     pub(crate) fn replace_child(&mut self, old: NodeId, new: NodeId) {
         let index = self
             .children()
@@ -290,6 +294,7 @@ mod node_tests {
         let parent_id: NodeId = NodeId {
             tree_id: ProcessUniqueId::new(),
             index: 0,
+            generation: 0,
         };
 
         node.set_parent(Some(parent_id.clone()));
@@ -299,6 +304,7 @@ mod node_tests {
     }
 
     #[test]
+    #[allow(clippy::get_first)]
     fn test_children() {
         let mut node = Node::new(5);
         assert_eq!(node.children().len(), 0);
@@ -306,6 +312,7 @@ mod node_tests {
         let child_id: NodeId = NodeId {
             tree_id: ProcessUniqueId::new(),
             index: 0,
+            generation: 0,
         };
         node.add_child(child_id.clone());
 
@@ -318,6 +325,7 @@ mod node_tests {
         let child_id: NodeId = NodeId {
             tree_id: ProcessUniqueId::new(),
             index: 0,
+            generation: 0,
         };
         node.children_mut().push(child_id.clone());
 
@@ -0,0 +1,248 @@
+use std::fmt;
+use std::sync::OnceLock;
+
+use serde::de::{DeserializeSeed, Error};
+use serde::{Deserialize, Deserializer};
+
+use snowflake::ProcessUniqueId;
+use NodeId;
+use Tree;
+
+// The tree id every deserialized `NodeId` gets.  No `Tree` ever has it, so these `NodeId`s have
+// to be rebound to a `Tree` before they can be used on it.
+pub(crate) fn detached_tree_id() -> ProcessUniqueId {
+    static DETACHED: OnceLock<ProcessUniqueId> = OnceLock::new();
+    *DETACHED.get_or_init(ProcessUniqueId::new)
+}
+
+// The fields of a `NodeId` as they are written by `Serialize`.
+#[derive(Deserialize)]
+#[serde(rename = "NodeId")]
+struct RawNodeId {
+    #[serde(rename = "tree_id")]
+    _tree_id: ProcessUniqueId,
+    index: usize,
+    // missing in `NodeId`s written before slots had generations
+    #[serde(default)]
+    generation: usize,
+}
+
+///
+/// A `NodeId` read on its own doesn't belong to any `Tree`, since the id of the `Tree` it was
+/// written with means nothing to the `Tree`s of this process.  Every `Tree` rejects it with
+/// `NodeIdError::InvalidNodeIdForTree` until it is rebound with `NodeIdSeed` or
+/// `Tree::translate_node_id`.
+///
+/// A `NodeId` written by a version that didn't have generations yet (1.8.0 and earlier) is read
+/// with a generation of 0.
+///
+impl<'de> Deserialize<'de> for NodeId {
+    fn deserialize<D>(deserializer: D) -> Result<NodeId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawNodeId::deserialize(deserializer)?;
+        Ok(NodeId {
+            tree_id: detached_tree_id(),
+            index: raw.index,
+            generation: raw.generation,
+        })
+    }
+}
+
+///
+/// Reads a `NodeId` that was written along with a `Tree` and binds it to the deserialized copy
+/// of that `Tree`.
+///
+/// Every deserialized `Tree` gets a fresh id, so the `NodeId`s that were written with it have to
+/// be read with a `NodeIdSeed` to be usable on it.  Returns an error if the `NodeId` doesn't point
+/// to a `Node` of the `Tree`.
+///
+/// **NOTE:** There is no way to tell which `Tree` a `NodeId` was written with, so this only gives
+/// meaningful results for `NodeId`s of the `Tree` that was written.
+///
+/// ```
+/// extern crate id_tree;
+/// extern crate serde;
+/// extern crate serde_json;
+///
+/// use id_tree::*;
+/// use id_tree::InsertBehavior::*;
+/// use serde::de::DeserializeSeed;
+///
+/// fn main() {
+///     let mut tree: Tree<i32> = Tree::new();
+///     let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+///     let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+///
+///     let tree_json = serde_json::to_string(&tree).unwrap();
+///     let id_json = serde_json::to_string(&child_id).unwrap();
+///
+///     let read: Tree<i32> = serde_json::from_str(&tree_json).unwrap();
+///     let read_child_id = NodeIdSeed::new(&read)
+///         .deserialize(&mut serde_json::Deserializer::from_str(&id_json))
+///         .unwrap();
+///
+///     assert_eq!(read.get(&read_child_id).unwrap().data(), &1);
+/// }
+/// ```
+///
+pub struct NodeIdSeed<'a, T: 'a> {
+    tree: &'a Tree<T>,
+}
+
+impl<'a, T> NodeIdSeed<'a, T> {
+    ///
+    /// Creates a new `NodeIdSeed` that binds `NodeId`s to the given `Tree`.
+    ///
+    pub fn new(tree: &'a Tree<T>) -> NodeIdSeed<'a, T> {
+        NodeIdSeed { tree }
+    }
+}
+
+impl<'a, T> Clone for NodeIdSeed<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for NodeIdSeed<'a, T> {}
+
+impl<'a, T> fmt::Debug for NodeIdSeed<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NodeIdSeed")
+            .field("tree_id", &self.tree.id)
+            .finish()
+    }
+}
+
+impl<'a, 'de, T> DeserializeSeed<'de> for NodeIdSeed<'a, T> {
+    type Value = NodeId;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<NodeId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let node_id = NodeId::deserialize(deserializer)?;
+        self.tree
+            .translate_node_id(&node_id)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    extern crate serde_json;
+
+    use self::serde_json::{Deserializer, Value};
+    use serde::de::DeserializeSeed;
+
+    use super::NodeIdSeed;
+    use InsertBehavior::*;
+    use Node;
+    use NodeId;
+    use NodeIdError;
+    use RemoveBehavior::*;
+    use Tree;
+
+    #[test]
+    fn test_fresh_tree_id() {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let json = serde_json::to_string(&tree).unwrap();
+
+        let first: Tree<i32> = serde_json::from_str(&json).unwrap();
+        let second: Tree<i32> = serde_json::from_str(&json).unwrap();
+        assert_ne!(first.id, tree.id);
+        assert_ne!(first.id, second.id);
+
+        let first_root_id = first.root_node_id().unwrap();
+        assert_eq!(first.get(first_root_id).unwrap().data(), &0);
+        assert_eq!(
+            second.get(first_root_id).unwrap_err(),
+            NodeIdError::InvalidNodeIdForTree
+        );
+        assert_eq!(
+            first.get(&root_id).unwrap_err(),
+            NodeIdError::InvalidNodeIdForTree
+        );
+    }
+
+    #[test]
+    fn test_node_id_seed() {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let removed_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let removed_json = serde_json::to_string(&removed_id).unwrap();
+        tree.remove_node(removed_id, DropChildren).unwrap();
+
+        let read: Tree<i32> = serde_json::from_str(&serde_json::to_string(&tree).unwrap()).unwrap();
+        let seed = NodeIdSeed::new(&read);
+
+        let json = serde_json::to_string(&child_id).unwrap();
+        let read_child_id = seed
+            .deserialize(&mut Deserializer::from_str(&json))
+            .unwrap();
+        assert_eq!(read.get(&read_child_id).unwrap().data(), &1);
+
+        // a plain `NodeId` belongs to no `Tree`
+        let detached: NodeId = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            read.get(&detached).unwrap_err(),
+            NodeIdError::InvalidNodeIdForTree
+        );
+        assert_eq!(read.translate_node_id(&detached).unwrap(), read_child_id);
+
+        assert!(seed
+            .deserialize(&mut Deserializer::from_str(&removed_json))
+            .is_err());
+    }
+
+    // removes the generations from a `Tree` or `NodeId` written by this version, which gives
+    // the layout written by 1.8.0
+    fn strip_generations(value: &mut Value) {
+        match *value {
+            Value::Object(ref mut map) => {
+                map.remove("generation");
+                map.remove("generations");
+                for field in map.values_mut() {
+                    strip_generations(field);
+                }
+            }
+            Value::Array(ref mut values) => {
+                for field in values.iter_mut() {
+                    strip_generations(field);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_format_without_generations() {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let removed_id = tree.insert(Node::new(3), UnderNode(&child_id)).unwrap();
+        tree.remove_node(removed_id, DropChildren).unwrap();
+
+        let mut value = serde_json::to_value(&tree).unwrap();
+        strip_generations(&mut value);
+        let mut id_value = serde_json::to_value(&child_id).unwrap();
+        strip_generations(&mut id_value);
+
+        let mut read: Tree<i32> = serde_json::from_value(value).unwrap();
+        assert_eq!(read.check_integrity(), Ok(()));
+        let read_child_id = NodeIdSeed::new(&read).deserialize(id_value).unwrap();
+        assert_eq!(read.get(&read_child_id).unwrap().data(), &1);
+
+        // the free slot can be re-used
+        let read_root_id = read.root_node_id().unwrap().clone();
+        let new_id = read.insert(Node::new(4), UnderNode(&read_root_id)).unwrap();
+        assert_eq!(read.get(&new_id).unwrap().data(), &4);
+        assert_eq!(read.len(), 4);
+    }
+}
@@ -0,0 +1,251 @@
+use InsertBehavior;
+use Node;
+use NodeId;
+use ParseError;
+use ParseErrorKind;
+use Tree;
+
+// The 4 character wide pieces `TreeFormatter` draws in front of a label, in both styles.
+const CONTINUATIONS: [&str; 3] = ["│   ", "|   ", "    "];
+const BRANCHES: [&str; 4] = ["├── ", "└── ", "|-- ", "`-- "];
+
+pub(crate) fn parse_formatted<T, E, F>(
+    s: &str,
+    mut parse_label: F,
+) -> Result<Tree<T>, ParseError<E>>
+where
+    F: FnMut(&str) -> Result<T, E>,
+{
+    let mut builder = Builder::new();
+
+    for (index, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut rest = line;
+        let mut depth = 0;
+        let mut column = 1;
+
+        loop {
+            if let Some(branch) = BRANCHES.iter().find(|branch| rest.starts_with(*branch)) {
+                rest = &rest[branch.len()..];
+                depth += 1;
+                column += 4;
+                break;
+            }
+
+            match CONTINUATIONS.iter().find(|piece| rest.starts_with(*piece)) {
+                Some(piece) => {
+                    rest = &rest[piece.len()..];
+                    depth += 1;
+                    column += 4;
+                }
+                None if depth == 0 => break,
+                // a continuation has to end in a branch
+                None => {
+                    return Err(ParseError::new(
+                        index + 1,
+                        column,
+                        ParseErrorKind::InvalidIndentation,
+                    ))
+                }
+            }
+        }
+
+        builder.add(index + 1, column, depth, rest, &mut parse_label)?;
+    }
+
+    Ok(builder.tree)
+}
+
+pub(crate) fn parse_indented<T, E, F>(s: &str, mut parse_label: F) -> Result<Tree<T>, ParseError<E>>
+where
+    F: FnMut(&str) -> Result<T, E>,
+{
+    let mut builder = Builder::new();
+    // the indentation of every `Node` on the path from the root to the last `Node` added
+    let mut indents: Vec<usize> = Vec::new();
+
+    for (index, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let label = line.trim_start_matches([' ', '\t']);
+        let indent = line[..line.len() - label.len()].chars().count();
+
+        let mut closed_indent = None;
+        while indents.last().is_some_and(|last| *last >= indent) {
+            closed_indent = indents.pop();
+        }
+        // going back up, the indentation has to match one of the levels above
+        if closed_indent.is_some_and(|closed| closed != indent) {
+            return Err(ParseError::new(
+                index + 1,
+                indent + 1,
+                ParseErrorKind::InvalidIndentation,
+            ));
+        }
+
+        let depth = indents.len();
+        indents.push(indent);
+        builder.add(index + 1, indent + 1, depth, label, &mut parse_label)?;
+    }
+
+    Ok(builder.tree)
+}
+
+// Inserts the `Node`s of a text layout that lists them in pre-order along with their depth.
+struct Builder<T> {
+    tree: Tree<T>,
+    path: Vec<NodeId>,
+}
+
+impl<T> Builder<T> {
+    fn new() -> Builder<T> {
+        Builder {
+            tree: Tree::new(),
+            path: Vec::new(),
+        }
+    }
+
+    fn add<E, F>(
+        &mut self,
+        line: usize,
+        column: usize,
+        depth: usize,
+        label: &str,
+        parse_label: &mut F,
+    ) -> Result<(), ParseError<E>>
+    where
+        F: FnMut(&str) -> Result<T, E>,
+    {
+        if depth == 0 && self.tree.root_node_id().is_some() {
+            return Err(ParseError::new(line, 1, ParseErrorKind::MultipleRoots));
+        }
+        if depth > self.path.len() {
+            return Err(ParseError::new(line, column, ParseErrorKind::MissingParent));
+        }
+
+        let data = parse_label(label)
+            .map_err(|error| ParseError::new(line, column, ParseErrorKind::InvalidLabel(error)))?;
+
+        self.path.truncate(depth);
+        let behavior = match self.path.last() {
+            Some(parent_id) => InsertBehavior::UnderNode(parent_id),
+            None => InsertBehavior::AsRoot,
+        };
+        let node_id = self
+            .tree
+            .insert(Node::new(data), behavior)
+            .expect("Builder::add: The parent of a new Node is always in the Tree.");
+        self.path.push(node_id);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use InsertBehavior::*;
+    use Node;
+    use ParseErrorKind::*;
+    use Tree;
+    use TreeFormatter;
+
+    fn sample() -> Tree<i32> {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let first_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let second_id = tree.insert(Node::new(2), UnderNode(&first_id)).unwrap();
+        tree.insert(Node::new(3), UnderNode(&second_id)).unwrap();
+        tree.insert(Node::new(4), UnderNode(&first_id)).unwrap();
+        tree.insert(Node::new(5), UnderNode(&root_id)).unwrap();
+        tree
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let tree = sample();
+
+        let mut s = String::new();
+        tree.write_formatted(&mut s).unwrap();
+        assert_eq!(s.parse::<Tree<i32>>().unwrap(), tree);
+
+        let mut s = String::new();
+        TreeFormatter::new()
+            .with_ascii(true)
+            .write(&tree, &mut s)
+            .unwrap();
+        assert_eq!(s.parse::<Tree<i32>>().unwrap(), tree);
+
+        let s = "0\n\t1\n\t\t2\n\t\t\t3\n\n\t\t4\n\t5\n";
+        let parsed = Tree::parse_indented(s, |label| label.parse::<i32>()).unwrap();
+        assert_eq!(parsed, tree);
+
+        assert_eq!("".parse::<Tree<i32>>().unwrap(), Tree::new());
+    }
+
+    #[test]
+    fn test_round_trip_strings() {
+        let tree = sample().map(|_, data| format!("node {}", data));
+
+        let mut s = String::new();
+        TreeFormatter::new().write(&tree, &mut s).unwrap();
+        assert_eq!(s.parse::<Tree<String>>().unwrap(), tree);
+
+        // `write_formatted` quotes the labels, so they have to be unquoted again
+        let mut s = String::new();
+        tree.write_formatted(&mut s).unwrap();
+        assert_ne!(s.parse::<Tree<String>>().unwrap(), tree);
+        let parsed =
+            Tree::parse_formatted(&s, |label| Ok::<_, ()>(label.trim_matches('"').to_string()))
+                .unwrap();
+        assert_eq!(parsed, tree);
+    }
+
+    #[test]
+    fn test_formatted_errors() {
+        let error = "0\n│   1\n".parse::<Tree<i32>>().unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 5));
+        assert_eq!(error.kind(), &InvalidIndentation);
+
+        let error = "0\n├── 1\n│   │   └── 2\n"
+            .parse::<Tree<i32>>()
+            .unwrap_err();
+        assert_eq!((error.line(), error.column()), (3, 13));
+        assert_eq!(error.kind(), &MissingParent);
+
+        let error = "0\n└── 1\n2\n".parse::<Tree<i32>>().unwrap_err();
+        assert_eq!((error.line(), error.column()), (3, 1));
+        assert_eq!(error.kind(), &MultipleRoots);
+
+        let error = "0\n└── 1\n    └── …2 more\n"
+            .parse::<Tree<i32>>()
+            .unwrap_err();
+        assert_eq!((error.line(), error.column()), (3, 9));
+        match *error.kind() {
+            InvalidLabel(_) => {}
+            ref kind => panic!("unexpected error kind: {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn test_indented_errors() {
+        let parse = |s| Tree::parse_indented(s, |label| label.parse::<i32>()).unwrap_err();
+
+        let error = parse("0\n    1\n  2\n");
+        assert_eq!((error.line(), error.column()), (3, 3));
+        assert_eq!(error.kind(), &InvalidIndentation);
+
+        let error = parse("  0\n1\n");
+        assert_eq!((error.line(), error.column()), (2, 1));
+        assert_eq!(error.kind(), &InvalidIndentation);
+
+        let error = parse("0\n  1\n0\n");
+        assert_eq!((error.line(), error.column()), (3, 1));
+        assert_eq!(error.kind(), &MultipleRoots);
+    }
+}
@@ -0,0 +1,1502 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use super::snowflake::ProcessUniqueId;
+use super::*;
+
+// The slots of a `PersistentTree` live in a persistent vector: a trie with `WIDTH` entries per
+// level.  Changing a slot copies the path from the trie root down to that slot and shares
+// everything else with the previous version.
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+///
+/// A `Node` of a `PersistentTree`.
+///
+/// The data of a `PersistentNode` is shared by every version of a `PersistentTree` that contains
+/// it.
+///
+#[derive(Debug)]
+pub struct PersistentNode<T> {
+    data: Rc<T>,
+    parent: Option<NodeId>,
+    // shared with the other versions as long as it isn't changed
+    children: Rc<Vec<NodeId>>,
+}
+
+impl<T> PersistentNode<T> {
+    ///
+    /// Returns an immutable reference to the data contained within the `PersistentNode`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let (tree, root_id) = PersistentTree::new().insert(Node::new(6), AsRoot).unwrap();
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().data(), &6);
+    /// ```
+    ///
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    ///
+    /// Returns a `Some` value containing the `NodeId` of this `PersistentNode`'s parent if it
+    /// exists; returns `None` if it does not.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let (tree, root_id) = PersistentTree::new().insert(Node::new(6), AsRoot).unwrap();
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().parent(), None);
+    /// ```
+    ///
+    pub fn parent(&self) -> Option<&NodeId> {
+        self.parent.as_ref()
+    }
+
+    ///
+    /// Returns an immutable reference to a `Vec` containing the `NodeId`s of this
+    /// `PersistentNode`'s children.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let (tree, root_id) = PersistentTree::new().insert(Node::new(6), AsRoot).unwrap();
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().children().len(), 0);
+    /// ```
+    ///
+    pub fn children(&self) -> &Vec<NodeId> {
+        &self.children
+    }
+}
+
+impl<T> Clone for PersistentNode<T> {
+    fn clone(&self) -> Self {
+        PersistentNode {
+            data: self.data.clone(),
+            parent: self.parent.clone(),
+            children: self.children.clone(),
+        }
+    }
+}
+
+struct Slot<T> {
+    generation: usize,
+    node: Option<PersistentNode<T>>,
+}
+
+impl<T> Clone for Slot<T> {
+    fn clone(&self) -> Self {
+        Slot {
+            generation: self.generation,
+            node: self.node.clone(),
+        }
+    }
+}
+
+enum Chunk<T> {
+    Branch(Vec<Rc<Chunk<T>>>),
+    Leaf(Vec<Slot<T>>),
+}
+
+impl<T> Clone for Chunk<T> {
+    fn clone(&self) -> Self {
+        match *self {
+            Chunk::Branch(ref children) => Chunk::Branch(children.clone()),
+            Chunk::Leaf(ref slots) => Chunk::Leaf(slots.clone()),
+        }
+    }
+}
+
+impl<T> Chunk<T> {
+    fn empty(shift: usize) -> Chunk<T> {
+        if shift == 0 {
+            Chunk::Leaf(Vec::with_capacity(WIDTH))
+        } else {
+            Chunk::Branch(Vec::with_capacity(WIDTH))
+        }
+    }
+
+    fn get(&self, index: usize, shift: usize) -> &Slot<T> {
+        match *self {
+            Chunk::Branch(ref children) => {
+                children[(index >> shift) & MASK].get(index, shift - BITS)
+            }
+            Chunk::Leaf(ref slots) => &slots[index & MASK],
+        }
+    }
+
+    fn get_mut(chunk: &mut Rc<Chunk<T>>, index: usize, shift: usize) -> &mut Slot<T> {
+        match *Rc::make_mut(chunk) {
+            Chunk::Branch(ref mut children) => {
+                Chunk::get_mut(&mut children[(index >> shift) & MASK], index, shift - BITS)
+            }
+            Chunk::Leaf(ref mut slots) => &mut slots[index & MASK],
+        }
+    }
+
+    fn push(chunk: &mut Rc<Chunk<T>>, index: usize, shift: usize, slot: Slot<T>) {
+        match *Rc::make_mut(chunk) {
+            Chunk::Branch(ref mut children) => {
+                let child_index = (index >> shift) & MASK;
+                if child_index == children.len() {
+                    children.push(Rc::new(Chunk::empty(shift - BITS)));
+                }
+                Chunk::push(&mut children[child_index], index, shift - BITS, slot);
+            }
+            Chunk::Leaf(ref mut slots) => slots.push(slot),
+        }
+    }
+}
+
+// A persistent stack of the indices of free slots.
+struct FreeId {
+    index: usize,
+    next: Option<Rc<FreeId>>,
+}
+
+impl Drop for FreeId {
+    fn drop(&mut self) {
+        // unlink iteratively so that dropping a long list can't overflow the stack
+        let mut next = self.next.take();
+        while let Some(free_id) = next {
+            match Rc::try_unwrap(free_id) {
+                Ok(mut free_id) => next = free_id.next.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+///
+/// A persistent version of a `Tree`.
+///
+/// A `PersistentTree` is never changed in place.  Instead, every modification returns a new
+/// version of the `PersistentTree` and leaves the old version untouched.  The new version shares
+/// all unchanged `Node`s (and their data, and their lists of children) with the old one, so a
+/// modification only copies O(log n) slots plus the lists of children that it changes: inserting
+/// a `Node` under a parent with k children takes O(log n + k) time and memory.  This makes it
+/// cheap to keep many snapshots around, for example to implement undo.
+///
+/// All versions derived from the same `PersistentTree` accept each other's `NodeId`s, as long as
+/// the `Node` they refer to is present in the version at hand.  Every inserted `Node` gets a
+/// `NodeId` that no other version has handed out, so modifying the same version more than once
+/// (starting a new branch of its history) is as cheap as modifying it the first time.
+///
+/// Use `PersistentTree::from` and `to_tree` to move between a `Tree` and a `PersistentTree`.  A
+/// `Tree` created with `to_tree` has a tree id of its own as well.
+///
+/// ```
+/// use id_tree::*;
+/// use id_tree::InsertBehavior::*;
+/// use id_tree::RemoveBehavior::*;
+///
+/// let (v1, root_id) = PersistentTree::new().insert(Node::new(0), AsRoot).unwrap();
+/// let (v2, child_id) = v1.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+/// let v3 = v2.remove_node(&child_id, DropChildren).unwrap();
+///
+/// assert_eq!(v1.get(&root_id).unwrap().children().len(), 0);
+/// assert_eq!(v2.get(&root_id).unwrap().children(), &vec![child_id.clone()]);
+/// assert!(v3.get(&child_id).is_err());
+///
+/// // v1 has been modified before, so this starts a new branch
+/// let (branch, other_child_id) = v1.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+/// assert_eq!(branch.get(&other_child_id).unwrap().parent(), Some(&root_id));
+/// assert!(branch.get(&child_id).is_err());
+/// assert!(v2.get(&other_child_id).is_err());
+/// ```
+///
+pub struct PersistentTree<T> {
+    id: ProcessUniqueId,
+    // Shared by all versions that were derived from the same `PersistentTree`.  Every `Node` that
+    // is inserted gets a generation of its own, so that versions that were modified independently
+    // of each other never hand out the same `NodeId` for different `Node`s.
+    next_generation: Rc<Cell<usize>>,
+    root: Option<NodeId>,
+    slots: Rc<Chunk<T>>,
+    shift: usize,
+    len: usize,
+    free_ids: Option<Rc<FreeId>>,
+}
+
+impl<T> PersistentTree<T> {
+    ///
+    /// Creates a new, empty `PersistentTree`.
+    ///
+    /// ```
+    /// use id_tree::PersistentTree;
+    ///
+    /// let _tree: PersistentTree<i32> = PersistentTree::new();
+    /// ```
+    ///
+    pub fn new() -> PersistentTree<T> {
+        PersistentTree {
+            id: ProcessUniqueId::new(),
+            next_generation: Rc::new(Cell::new(0)),
+            root: None,
+            slots: Rc::new(Chunk::empty(0)),
+            shift: 0,
+            len: 0,
+            free_ids: None,
+        }
+    }
+
+    ///
+    /// Returns a `Some` value containing the `NodeId` of the root `PersistentNode` if it exists.
+    /// Otherwise a `None` value is returned.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let (tree, root_id) = PersistentTree::new().insert(Node::new(5), AsRoot).unwrap();
+    ///
+    /// assert_eq!(&root_id, tree.root_node_id().unwrap());
+    /// ```
+    ///
+    pub fn root_node_id(&self) -> Option<&NodeId> {
+        self.root.as_ref()
+    }
+
+    ///
+    /// Get an immutable reference to a `PersistentNode`.
+    ///
+    /// Returns a `Result` containing the immutable reference or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let (tree, root_id) = PersistentTree::new().insert(Node::new(5), AsRoot).unwrap();
+    ///
+    /// let root_node: &PersistentNode<i32> = tree.get(&root_id).unwrap();
+    ///
+    /// # assert_eq!(root_node.data(), &5);
+    /// ```
+    ///
+    pub fn get(&self, node_id: &NodeId) -> Result<&PersistentNode<T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if is_valid {
+            Ok(self.get_unsafe(node_id))
+        } else {
+            Err(error.expect(
+                "PersistentTree::get: Missing an error value on finding an invalid NodeId.",
+            ))
+        }
+    }
+
+    /// Inserts a new `Node` into the `PersistentTree`.  The `InsertBehavior` provided will
+    /// determine where the `Node` is inserted.
+    ///
+    /// Returns a `Result` containing the new version of the `PersistentTree` along with the
+    /// `NodeId` of the `Node` that was inserted, or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let tree: PersistentTree<i32> = PersistentTree::new();
+    ///
+    /// let (tree, root_id) = tree.insert(Node::new(1), AsRoot).unwrap();
+    /// let (tree, child_id) = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    ///
+    /// # assert_eq!(tree.get(&child_id).unwrap().parent(), Some(&root_id));
+    /// ```
+    ///
+    pub fn insert(
+        &self,
+        node: Node<T>,
+        behavior: InsertBehavior,
+    ) -> Result<(PersistentTree<T>, NodeId), NodeIdError> {
+        let mut tree = self.clone();
+        let data = Rc::new(node.data);
+
+        let node_id = match behavior {
+            InsertBehavior::UnderNode(parent_id) => {
+                let (is_valid, error) = self.is_valid_node_id(parent_id);
+                if !is_valid {
+                    return Err(error.expect(
+                        "PersistentTree::insert: Missing an error value but found an invalid \
+                         NodeId.",
+                    ));
+                }
+                let index = self.get_unsafe(parent_id).children.len();
+                tree.insert_with_parent_at(data, parent_id, index)
+            }
+            InsertBehavior::UnderNodeAt(parent_id, index) => {
+                let (is_valid, error) = self.is_valid_node_id(parent_id);
+                if !is_valid {
+                    return Err(error.expect(
+                        "PersistentTree::insert: Missing an error value but found an invalid \
+                         NodeId.",
+                    ));
+                }
+                if index > self.get_unsafe(parent_id).children.len() {
+                    return Err(NodeIdError::InvalidChildIndex);
+                }
+                tree.insert_with_parent_at(data, parent_id, index)
+            }
+            InsertBehavior::BeforeSibling(sibling_id) => {
+                let (parent_id, index) = self.parent_and_position(sibling_id)?;
+                tree.insert_with_parent_at(data, &parent_id, index)
+            }
+            InsertBehavior::AfterSibling(sibling_id) => {
+                let (parent_id, index) = self.parent_and_position(sibling_id)?;
+                tree.insert_with_parent_at(data, &parent_id, index + 1)
+            }
+            InsertBehavior::AsRoot => tree.set_root(data),
+            InsertBehavior::AsNewRoot => {
+                let node_id = tree.insert_new_node(data);
+                if tree.root.is_none() {
+                    tree.root = Some(node_id.clone());
+                }
+                node_id
+            }
+        };
+
+        Ok((tree, node_id))
+    }
+
+    /// Removes a `Node` from the `PersistentTree`.  The `RemoveBehavior` provided determines what
+    /// happens to the removed `Node`'s children.
+    ///
+    /// Returns a `Result` containing the new version of the `PersistentTree`, or a `NodeIdError`
+    /// if one occurred.
+    ///
+    /// **NOTE:** Unlike `Tree::remove_node`, this takes the `NodeId` by reference, since it stays
+    /// valid for older versions of the `PersistentTree`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    /// use id_tree::RemoveBehavior::*;
+    ///
+    /// let (tree, root_id) = PersistentTree::new().insert(Node::new(0), AsRoot).unwrap();
+    /// let (tree, child_id) = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let (tree, grandchild_id) = tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+    ///
+    /// let lifted = tree.remove_node(&child_id, LiftChildren).unwrap();
+    ///
+    /// assert!(lifted.get(&child_id).is_err());
+    /// assert_eq!(lifted.get(&grandchild_id).unwrap().parent(), Some(&root_id));
+    /// # assert_eq!(tree.get(&grandchild_id).unwrap().parent(), Some(&child_id));
+    /// ```
+    ///
+    pub fn remove_node(
+        &self,
+        node_id: &NodeId,
+        behavior: RemoveBehavior,
+    ) -> Result<PersistentTree<T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "PersistentTree::remove_node: Missing an error value but found an invalid \
+                 NodeId.",
+            ));
+        }
+
+        let mut tree = self.clone();
+        match behavior {
+            RemoveBehavior::DropChildren => tree.remove_node_drop_children(node_id),
+            RemoveBehavior::LiftChildren => tree.remove_node_lift_children(node_id),
+            RemoveBehavior::OrphanChildren => tree.remove_node_orphan_children(node_id),
+        }
+
+        Ok(tree)
+    }
+
+    /// Moves a `Node` in the `PersistentTree` to a new location based upon the `MoveBehavior`
+    /// provided.
+    ///
+    /// Returns a `Result` containing the new version of the `PersistentTree`, or a `NodeIdError`
+    /// if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    /// use id_tree::MoveBehavior::*;
+    ///
+    /// let (tree, root_id) = PersistentTree::new().insert(Node::new(1), AsRoot).unwrap();
+    /// let (tree, child_id) = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let (tree, grandchild_id) = tree.insert(Node::new(3), UnderNode(&child_id)).unwrap();
+    ///
+    /// let moved = tree.move_node(&grandchild_id, ToRoot).unwrap();
+    ///
+    /// assert_eq!(moved.root_node_id(), Some(&grandchild_id));
+    /// # assert!(moved.get(&grandchild_id).unwrap().children().contains(&root_id));
+    /// # assert_eq!(tree.root_node_id(), Some(&root_id));
+    /// ```
+    ///
+    pub fn move_node(
+        &self,
+        node_id: &NodeId,
+        behavior: MoveBehavior,
+    ) -> Result<PersistentTree<T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "PersistentTree::move_node: Missing an error value on finding an invalid NodeId.",
+            ));
+        }
+
+        let mut tree = self.clone();
+        match behavior {
+            MoveBehavior::ToRoot => tree.move_node_to_root(node_id),
+            MoveBehavior::ToParent(parent_id) => {
+                let (is_valid, error) = self.is_valid_node_id(parent_id);
+                if !is_valid {
+                    return Err(error.expect(
+                        "PersistentTree::move_node: Missing an error value on finding an \
+                         invalid NodeId.",
+                    ));
+                }
+                tree.move_node_to_parent(node_id, parent_id)
+            }
+            MoveBehavior::ToNewRoot => {
+                if let Some(parent_id) = tree.get_unsafe(node_id).parent.clone() {
+                    tree.detach_from_parent(&parent_id, node_id);
+                    tree.set_parent(node_id, None);
+                }
+                if tree.root.is_none() {
+                    tree.root = Some(node_id.clone());
+                }
+            }
+        }
+
+        Ok(tree)
+    }
+
+    /// Swaps two `Node`s in the `PersistentTree` based upon the `SwapBehavior` provided.
+    ///
+    /// Returns a `Result` containing the new version of the `PersistentTree`, or a `NodeIdError`
+    /// if one occurred on either provided `NodeId`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    /// use id_tree::SwapBehavior::*;
+    ///
+    /// let (tree, root_id) = PersistentTree::new().insert(Node::new(1), AsRoot).unwrap();
+    /// let (tree, first_child_id) = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let (tree, second_child_id) = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let swapped = tree.swap_nodes(&first_child_id, &second_child_id, TakeChildren).unwrap();
+    ///
+    /// assert_eq!(
+    ///     swapped.get(&root_id).unwrap().children(),
+    ///     &vec![second_child_id, first_child_id]
+    /// );
+    /// ```
+    ///
+    pub fn swap_nodes(
+        &self,
+        first_id: &NodeId,
+        second_id: &NodeId,
+        behavior: SwapBehavior,
+    ) -> Result<PersistentTree<T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(first_id);
+        if !is_valid {
+            return Err(error.expect(
+                "PersistentTree::swap_nodes: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        let (is_valid, error) = self.is_valid_node_id(second_id);
+        if !is_valid {
+            return Err(error.expect(
+                "PersistentTree::swap_nodes: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        let mut tree = self.clone();
+        match behavior {
+            SwapBehavior::TakeChildren => tree.swap_nodes_take_children(first_id, second_id),
+            SwapBehavior::LeaveChildren => tree.swap_nodes_leave_children(first_id, second_id),
+            SwapBehavior::ChildrenOnly => tree.swap_nodes_children_only(first_id, second_id),
+        }
+
+        Ok(tree)
+    }
+
+    ///
+    /// Creates a `Tree` with the same content as this version of the `PersistentTree`.
+    ///
+    /// The new `Tree` has a tree id of its own, but exactly the same slot layout as this version,
+    /// so `NodeId`s of this version can be turned into `NodeId`s of the new `Tree` with
+    /// `Tree::translate_node_id`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let (persistent, root_id) = PersistentTree::new().insert(Node::new(1), AsRoot).unwrap();
+    ///
+    /// let tree: Tree<i32> = persistent.to_tree();
+    /// let tree_root_id = tree.translate_node_id(&root_id).unwrap();
+    ///
+    /// assert_eq!(tree.get(&tree_root_id).unwrap().data(), &1);
+    /// ```
+    ///
+    pub fn to_tree(&self) -> Tree<T>
+    where
+        T: Clone,
+    {
+        let mut nodes = Vec::with_capacity(self.len);
+        let mut generations = Vec::with_capacity(self.len);
+
+        for index in 0..self.len {
+            let slot = self.slots.get(index, self.shift);
+            nodes.push(slot.node.as_ref().map(|node| Node {
+                data: (*node.data).clone(),
+                parent: node.parent.clone(),
+                children: node.children.to_vec(),
+            }));
+            generations.push(slot.generation);
+        }
+
+        let mut free_ids = Vec::new();
+        let mut free_id = self.free_ids.as_ref();
+        while let Some(current) = free_id {
+            free_ids.push(self.new_node_id(current.index));
+            free_id = current.next.as_ref();
+        }
+        // a `Tree` hands out the last free id first
+        free_ids.reverse();
+
+        Tree::with_layout_of(nodes, self.root.clone(), generations, free_ids)
+    }
+
+    fn set_root(&mut self, data: Rc<T>) -> NodeId {
+        let new_root_id = self.insert_new_node(data);
+
+        if let Some(current_root_node_id) = self.root.clone() {
+            self.set_as_parent_and_child(&new_root_id, &current_root_node_id);
+        }
+
+        self.root = Some(new_root_id.clone());
+        new_root_id
+    }
+
+    fn insert_with_parent_at(&mut self, data: Rc<T>, parent_id: &NodeId, index: usize) -> NodeId {
+        let new_child_id = self.insert_new_node(data);
+        self.children_mut(parent_id)
+            .insert(index, new_child_id.clone());
+        self.set_parent(&new_child_id, Some(parent_id.clone()));
+        new_child_id
+    }
+
+    fn parent_and_position(&self, node_id: &NodeId) -> Result<(NodeId, usize), NodeIdError> {
+        let parent_id = self
+            .get(node_id)?
+            .parent()
+            .cloned()
+            .ok_or(NodeIdError::NodeHasNoParent)?;
+        let index = self.position_in_parent(&parent_id, node_id);
+        Ok((parent_id, index))
+    }
+
+    fn remove_node_lift_children(&mut self, node_id: &NodeId) {
+        if let Some(parent_id) = self.get_unsafe(node_id).parent.clone() {
+            // attach children to parent
+            for child_id in self.get_unsafe(node_id).children.clone().iter() {
+                self.set_as_parent_and_child(&parent_id, child_id);
+            }
+        } else {
+            self.set_parent_of_children(node_id, None);
+        }
+
+        self.remove_node_internal(node_id);
+    }
+
+    fn remove_node_orphan_children(&mut self, node_id: &NodeId) {
+        self.set_parent_of_children(node_id, None);
+        self.remove_node_internal(node_id);
+    }
+
+    fn remove_node_drop_children(&mut self, node_id: &NodeId) {
+        let mut descendants = self.get_unsafe(node_id).children.to_vec();
+        self.remove_node_internal(node_id);
+
+        while let Some(descendant_id) = descendants.pop() {
+            let descendant = self.take_node(&descendant_id);
+            descendants.extend(descendant.children.iter().cloned());
+        }
+    }
+
+    fn move_node_to_parent(&mut self, node_id: &NodeId, parent_id: &NodeId) {
+        if let Some(subtree_root_id) = self.find_subtree_root_between_ids(parent_id, node_id) {
+            // node_id is above parent_id, this is a move "down" the tree.
+
+            if self.root.as_ref() == Some(node_id) {
+                // we're moving the root down the tree.
+
+                // detach subtree_root from node
+                self.detach_from_parent(node_id, &subtree_root_id);
+
+                // set subtree_root as Tree root.
+                self.set_parent(&subtree_root_id, None);
+                self.root = Some(subtree_root_id);
+
+                self.set_as_parent_and_child(parent_id, node_id);
+            } else {
+                // we're moving some other node down the tree.
+
+                if let Some(old_parent) = self.get_unsafe(node_id).parent.clone() {
+                    // detach from old parent
+                    self.detach_from_parent(&old_parent, node_id);
+                    // connect old parent and subtree root
+                    self.set_as_parent_and_child(&old_parent, &subtree_root_id);
+                } else {
+                    // node is orphaned, need to set subtree_root's parent to None (same as node's)
+                    self.set_parent(&subtree_root_id, None);
+                }
+                // detach subtree_root from node
+                self.detach_from_parent(node_id, &subtree_root_id);
+
+                self.set_as_parent_and_child(parent_id, node_id);
+            }
+        } else {
+            // this is a move "across" or "up" the tree.
+
+            // detach from old parent
+            if let Some(old_parent) = self.get_unsafe(node_id).parent.clone() {
+                self.detach_from_parent(&old_parent, node_id);
+            }
+
+            self.set_as_parent_and_child(parent_id, node_id);
+        }
+    }
+
+    fn move_node_to_root(&mut self, node_id: &NodeId) {
+        let old_root = self.root.clone();
+
+        if let Some(parent_id) = self.get_unsafe(node_id).parent.clone() {
+            self.detach_from_parent(&parent_id, node_id);
+        }
+        self.set_parent(node_id, None);
+        self.root = Some(node_id.clone());
+
+        if let Some(old_root) = old_root {
+            self.move_node_to_parent(&old_root, node_id);
+        }
+    }
+
+    fn swap_nodes_take_children(&mut self, first_id: &NodeId, second_id: &NodeId) {
+        let lower_upper_test = if self
+            .find_subtree_root_between_ids(first_id, second_id)
+            .is_some()
+        {
+            Some((first_id, second_id))
+        } else if self
+            .find_subtree_root_between_ids(second_id, first_id)
+            .is_some()
+        {
+            Some((second_id, first_id))
+        } else {
+            None
+        };
+
+        if let Some((lower_id, upper_id)) = lower_upper_test {
+            let upper_parent_id = self.get_unsafe(upper_id).parent.clone();
+            // lower is lower, so it has a parent for sure
+            let lower_parent_id = self.get_unsafe(lower_id).parent.clone().unwrap();
+
+            self.set_parent(lower_id, upper_parent_id.clone());
+            self.detach_from_parent(&lower_parent_id, lower_id);
+
+            if let Some(ref upper_parent_id) = upper_parent_id {
+                self.replace_child(upper_parent_id, upper_id, lower_id.clone());
+            } else if self.root.as_ref() == Some(upper_id) {
+                self.root = Some(lower_id.clone());
+            }
+
+            self.set_parent(upper_id, Some(lower_id.clone()));
+            self.children_mut(lower_id).push(upper_id.clone());
+        } else {
+            // just across
+
+            let first_parent_id = self.get_unsafe(first_id).parent.clone();
+            let second_parent_id = self.get_unsafe(second_id).parent.clone();
+
+            if first_parent_id == second_parent_id {
+                if let Some(parent_id) = first_parent_id {
+                    // same parent
+                    let first_index = self.position_in_parent(&parent_id, first_id);
+                    let second_index = self.position_in_parent(&parent_id, second_id);
+
+                    self.children_mut(&parent_id)
+                        .swap(first_index, second_index);
+                }
+            } else {
+                // replace parents
+                self.set_parent(first_id, second_parent_id.clone());
+                self.set_parent(second_id, first_parent_id.clone());
+
+                // change children
+                if let Some(ref first_parent_id) = first_parent_id {
+                    self.replace_child(first_parent_id, first_id, second_id.clone());
+                }
+                if let Some(ref second_parent_id) = second_parent_id {
+                    self.replace_child(second_parent_id, second_id, first_id.clone());
+                }
+            }
+        }
+    }
+
+    fn swap_nodes_leave_children(&mut self, first_id: &NodeId, second_id: &NodeId) {
+        //take care of these nodes' children's parent values
+        self.set_parent_of_children(first_id, Some(second_id.clone()));
+        self.set_parent_of_children(second_id, Some(first_id.clone()));
+
+        //swap children of these nodes
+        let first_children = self.get_unsafe(first_id).children.clone();
+        let second_children = self.get_unsafe(second_id).children.clone();
+        self.get_mut_unsafe(first_id).children = second_children;
+        self.get_mut_unsafe(second_id).children = first_children;
+
+        let first_parent = self.get_unsafe(first_id).parent.clone();
+        let second_parent = self.get_unsafe(second_id).parent.clone();
+
+        match (first_parent, second_parent) {
+            (Some(ref first_parent_id), Some(ref second_parent_id)) => {
+                let first_index = self.position_in_parent(first_parent_id, first_id);
+                let second_index = self.position_in_parent(second_parent_id, second_id);
+
+                self.children_mut(first_parent_id)[first_index] = second_id.clone();
+                self.children_mut(second_parent_id)[second_index] = first_id.clone();
+
+                self.set_parent(first_id, Some(second_parent_id.clone()));
+                self.set_parent(second_id, Some(first_parent_id.clone()));
+            }
+            (Some(ref first_parent_id), None) => {
+                let first_index = self.position_in_parent(first_parent_id, first_id);
+                self.children_mut(first_parent_id)[first_index] = second_id.clone();
+
+                self.set_parent(first_id, None);
+                self.set_parent(second_id, Some(first_parent_id.clone()));
+
+                if self.root.as_ref() == Some(second_id) {
+                    self.root = Some(first_id.clone());
+                }
+            }
+            (None, Some(ref second_parent_id)) => {
+                let second_index = self.position_in_parent(second_parent_id, second_id);
+                self.children_mut(second_parent_id)[second_index] = first_id.clone();
+
+                self.set_parent(first_id, Some(second_parent_id.clone()));
+                self.set_parent(second_id, None);
+
+                if self.root.as_ref() == Some(first_id) {
+                    self.root = Some(second_id.clone());
+                }
+            }
+            (None, None) => {
+                if self.root.as_ref() == Some(first_id) {
+                    self.root = Some(second_id.clone());
+                } else if self.root.as_ref() == Some(second_id) {
+                    self.root = Some(first_id.clone());
+                }
+            }
+        }
+    }
+
+    fn swap_nodes_children_only(&mut self, first_id: &NodeId, second_id: &NodeId) {
+        let lower_upper_test = if self
+            .find_subtree_root_between_ids(first_id, second_id)
+            .is_some()
+        {
+            Some((first_id, second_id))
+        } else if self
+            .find_subtree_root_between_ids(second_id, first_id)
+            .is_some()
+        {
+            Some((second_id, first_id))
+        } else {
+            None
+        };
+
+        let first_children = self.get_unsafe(first_id).children.clone();
+        let second_children = self.get_unsafe(second_id).children.clone();
+
+        if let Some((lower_id, upper_id)) = lower_upper_test {
+            let lower_parent = self.get_unsafe(lower_id).parent.clone().unwrap();
+
+            let (mut upper_children, lower_children) = if upper_id == first_id {
+                (first_children, second_children)
+            } else {
+                (second_children, first_children)
+            };
+
+            for child in upper_children.iter() {
+                self.set_parent(child, Some(lower_id.clone()));
+            }
+            for child in lower_children.iter() {
+                self.set_parent(child, Some(upper_id.clone()));
+            }
+
+            if upper_id == &lower_parent {
+                // direct child
+                Rc::make_mut(&mut upper_children).retain(|id| id != lower_id);
+            } else {
+                self.detach_from_parent(&lower_parent, lower_id);
+            }
+
+            //swap children of these nodes
+            self.get_mut_unsafe(upper_id).children = lower_children;
+            self.get_mut_unsafe(lower_id).children = upper_children;
+
+            //add lower to upper
+            self.set_as_parent_and_child(upper_id, lower_id);
+        } else {
+            //just across
+
+            //take care of these nodes' children's parent values
+            for child in first_children.iter() {
+                self.set_parent(child, Some(second_id.clone()));
+            }
+            for child in second_children.iter() {
+                self.set_parent(child, Some(first_id.clone()));
+            }
+
+            //swap children of these nodes
+            self.get_mut_unsafe(first_id).children = second_children;
+            self.get_mut_unsafe(second_id).children = first_children;
+        }
+    }
+
+    // Nothing should make it past this function.
+    // If there is a way for a NodeId to be invalid, it should be caught here.
+    fn is_valid_node_id(&self, node_id: &NodeId) -> (bool, Option<NodeIdError>) {
+        if node_id.tree_id != self.id {
+            return (false, Some(NodeIdError::InvalidNodeIdForTree));
+        }
+
+        if node_id.index >= self.len {
+            // handed out by a version that has more slots than this one
+            return (false, Some(NodeIdError::NodeIdNoLongerValid));
+        }
+
+        let slot = self.slots.get(node_id.index, self.shift);
+        if slot.node.is_none() || slot.generation != node_id.generation {
+            return (false, Some(NodeIdError::NodeIdNoLongerValid));
+        }
+
+        (true, None)
+    }
+
+    fn find_subtree_root_between_ids(
+        &self,
+        lower_id: &NodeId,
+        upper_id: &NodeId,
+    ) -> Option<NodeId> {
+        let mut lower_id = lower_id;
+        while let Some(lower_parent) = self.get_unsafe(lower_id).parent.as_ref() {
+            if lower_parent == upper_id {
+                return Some(lower_id.clone());
+            }
+            lower_id = lower_parent;
+        }
+
+        // lower_id has no parent, it can't be below upper_id
+        None
+    }
+
+    fn position_in_parent(&self, parent_id: &NodeId, node_id: &NodeId) -> usize {
+        self.get_unsafe(parent_id)
+            .children
+            .iter()
+            .position(|id| id == node_id)
+            .expect("PersistentTree::position_in_parent: Node is not a child of its parent.")
+    }
+
+    fn set_as_parent_and_child(&mut self, parent_id: &NodeId, child_id: &NodeId) {
+        self.children_mut(parent_id).push(child_id.clone());
+        self.set_parent(child_id, Some(parent_id.clone()));
+    }
+
+    fn detach_from_parent(&mut self, parent_id: &NodeId, node_id: &NodeId) {
+        self.children_mut(parent_id)
+            .retain(|child_id| child_id != node_id);
+    }
+
+    fn replace_child(&mut self, parent_id: &NodeId, old: &NodeId, new: NodeId) {
+        let index = self.position_in_parent(parent_id, old);
+        self.children_mut(parent_id)[index] = new;
+    }
+
+    fn insert_new_node(&mut self, data: Rc<T>) -> NodeId {
+        let new_node = PersistentNode {
+            data,
+            parent: None,
+            children: Rc::new(Vec::new()),
+        };
+        let generation = self.take_generation();
+
+        if let Some(free_id) = self.free_ids.take() {
+            self.free_ids = free_id.next.clone();
+
+            let slot = Chunk::get_mut(&mut self.slots, free_id.index, self.shift);
+            slot.generation = generation;
+            slot.node = Some(new_node);
+            self.new_node_id(free_id.index)
+        } else {
+            let new_node_index = self.len;
+            self.push_slot(Slot {
+                generation,
+                node: Some(new_node),
+            });
+
+            self.new_node_id(new_node_index)
+        }
+    }
+
+    fn take_generation(&self) -> usize {
+        let generation = self.next_generation.get();
+        self.next_generation.set(generation.wrapping_add(1));
+        generation
+    }
+
+    fn push_slot(&mut self, slot: Slot<T>) {
+        if self.len == WIDTH << self.shift {
+            // the trie is full, add another level on top
+            let old_slots = self.slots.clone();
+            self.slots = Rc::new(Chunk::Branch(vec![old_slots]));
+            self.shift += BITS;
+        }
+
+        Chunk::push(&mut self.slots, self.len, self.shift, slot);
+        self.len += 1;
+    }
+
+    fn remove_node_internal(&mut self, node_id: &NodeId) {
+        if self.root.as_ref() == Some(node_id) {
+            self.root = None;
+        }
+
+        let node = self.take_node(node_id);
+        if let Some(parent_id) = node.parent {
+            self.detach_from_parent(&parent_id, node_id);
+        }
+    }
+
+    fn take_node(&mut self, node_id: &NodeId) -> PersistentNode<T> {
+        // a free slot gets a generation that no `Node` has, so that a `Tree` created with `to_tree`
+        // doesn't hand out a `NodeId` of a removed `Node` again
+        let generation = self.take_generation();
+        let node = {
+            let slot = Chunk::get_mut(&mut self.slots, node_id.index, self.shift);
+            slot.generation = generation;
+            slot.node.take().expect(
+                "PersistentTree::take_node: An invalid NodeId made it past id_tree's internal \
+                 checks.  Please report this issue!",
+            )
+        };
+
+        self.free_ids = Some(Rc::new(FreeId {
+            index: node_id.index,
+            next: self.free_ids.take(),
+        }));
+
+        node
+    }
+
+    fn new_node_id(&self, node_index: usize) -> NodeId {
+        NodeId {
+            tree_id: self.id,
+            index: node_index,
+            generation: self.slots.get(node_index, self.shift).generation,
+        }
+    }
+
+    fn set_parent(&mut self, node_id: &NodeId, new_parent: Option<NodeId>) {
+        self.get_mut_unsafe(node_id).parent = new_parent;
+    }
+
+    fn set_parent_of_children(&mut self, node_id: &NodeId, new_parent: Option<NodeId>) {
+        for child_id in self.get_unsafe(node_id).children.clone().iter() {
+            self.set_parent(child_id, new_parent.clone());
+        }
+    }
+
+    fn get_unsafe(&self, node_id: &NodeId) -> &PersistentNode<T> {
+        self.slots
+            .get(node_id.index, self.shift)
+            .node
+            .as_ref()
+            .expect(
+                "PersistentTree::get_unsafe: An invalid NodeId made it past id_tree's internal \
+                 checks.  Please report this issue!",
+            )
+    }
+
+    fn children_mut(&mut self, node_id: &NodeId) -> &mut Vec<NodeId> {
+        Rc::make_mut(&mut self.get_mut_unsafe(node_id).children)
+    }
+
+    fn get_mut_unsafe(&mut self, node_id: &NodeId) -> &mut PersistentNode<T> {
+        Chunk::get_mut(&mut self.slots, node_id.index, self.shift)
+            .node
+            .as_mut()
+            .expect(
+                "PersistentTree::get_mut_unsafe: An invalid NodeId made it past id_tree's \
+                 internal checks.  Please report this issue!",
+            )
+    }
+}
+
+impl<T> Default for PersistentTree<T> {
+    fn default() -> Self {
+        PersistentTree::new()
+    }
+}
+
+impl<T> Clone for PersistentTree<T> {
+    fn clone(&self) -> Self {
+        PersistentTree {
+            id: self.id,
+            next_generation: self.next_generation.clone(),
+            root: self.root.clone(),
+            slots: self.slots.clone(),
+            shift: self.shift,
+            len: self.len,
+            free_ids: self.free_ids.clone(),
+        }
+    }
+}
+
+impl<T> From<Tree<T>> for PersistentTree<T> {
+    ///
+    /// Turns a `Tree` into a `PersistentTree`.  All `NodeId`s of the `Tree` are valid for the
+    /// new `PersistentTree`.
+    ///
+    fn from(tree: Tree<T>) -> PersistentTree<T> {
+        let mut persistent = PersistentTree::new();
+        persistent.id = tree.id;
+        persistent.root = tree.root;
+
+        // new `Node`s must not get a `NodeId` the `Tree` has handed out already, including the
+        // ones of undone insertions
+        let next_generation = tree
+            .generations
+            .iter()
+            .map(|generation| generation.wrapping_add(1))
+            .chain(tree.min_generations.iter().cloned())
+            .max()
+            .unwrap_or(0);
+        persistent.next_generation.set(next_generation);
+
+        for (node, generation) in tree.nodes.into_iter().zip(tree.generations) {
+            persistent.push_slot(Slot {
+                generation,
+                node: node.map(|node| PersistentNode {
+                    data: Rc::new(node.data),
+                    parent: node.parent,
+                    children: Rc::new(node.children),
+                }),
+            });
+        }
+
+        // the last free id of a `Tree` is handed out first, so it goes on top
+        for free_id in tree.free_ids {
+            persistent.free_ids = Some(Rc::new(FreeId {
+                index: free_id.index,
+                next: persistent.free_ids.take(),
+            }));
+        }
+
+        persistent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PersistentTree;
+    use super::WIDTH;
+    use std::rc::Rc;
+    use InsertBehavior::*;
+    use MoveBehavior::*;
+    use Node;
+    use NodeId;
+    use RemoveBehavior::*;
+    use SwapBehavior::*;
+    use Tree;
+
+    type Layout = Vec<Option<(i32, Option<usize>, Vec<usize>)>>;
+
+    // Describes a `Tree` slot by slot, ignoring the tree id so that unrelated `Tree`s can be
+    // compared.
+    fn layout(tree: &Tree<i32>) -> (Option<usize>, Layout, Vec<usize>) {
+        let index = |id: &NodeId| id.index;
+        let nodes = tree
+            .nodes
+            .iter()
+            .map(|node| {
+                node.as_ref().map(|node| {
+                    (
+                        *node.data(),
+                        node.parent().map(index),
+                        node.children().iter().map(index).collect(),
+                    )
+                })
+            })
+            .collect();
+
+        (
+            tree.root_node_id().map(index),
+            nodes,
+            tree.generations.clone(),
+        )
+    }
+
+    // Like `layout`, but leaves out the generations, which a `PersistentTree` hands out
+    // differently.
+    fn structure(tree: &Tree<i32>) -> (Option<usize>, Layout) {
+        let (root, nodes, _) = layout(tree);
+        (root, nodes)
+    }
+
+    #[test]
+    fn test_insert_keeps_old_versions() {
+        let v0: PersistentTree<i32> = PersistentTree::new();
+        let (v1, root_id) = v0.insert(Node::new(0), AsRoot).unwrap();
+        let (v2, child_id) = v1.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let (v3, new_root_id) = v2.insert(Node::new(2), AsRoot).unwrap();
+
+        assert_eq!(v0.root_node_id(), None);
+        assert!(v0.get(&root_id).is_err());
+
+        assert!(v1.get(&root_id).unwrap().children().is_empty());
+        assert!(v1.get(&child_id).is_err());
+
+        assert_eq!(v2.root_node_id(), Some(&root_id));
+        assert_eq!(
+            v2.get(&root_id).unwrap().children(),
+            &vec![child_id.clone()]
+        );
+
+        assert_eq!(v3.root_node_id(), Some(&new_root_id));
+        assert_eq!(v3.get(&root_id).unwrap().parent(), Some(&new_root_id));
+    }
+
+    #[test]
+    fn test_new_roots() {
+        let (v1, root_id) = PersistentTree::new()
+            .insert(Node::new(0), AsNewRoot)
+            .unwrap();
+        let (v2, other_id) = v1.insert(Node::new(1), AsNewRoot).unwrap();
+        let (v3, child_id) = v2.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let v4 = v3.move_node(&child_id, ToNewRoot).unwrap();
+
+        assert_eq!(v1.root_node_id(), Some(&root_id));
+        assert_eq!(v2.root_node_id(), Some(&root_id));
+        assert_eq!(v2.get(&other_id).unwrap().parent(), None);
+        assert_eq!(v4.get(&child_id).unwrap().parent(), None);
+        assert!(v4.get(&root_id).unwrap().children().is_empty());
+        assert_eq!(v3.get(&child_id).unwrap().parent(), Some(&root_id));
+    }
+
+    #[test]
+    fn test_data_is_shared() {
+        let (v1, root_id) = PersistentTree::new().insert(Node::new(0), AsRoot).unwrap();
+        let (v2, _) = v1.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+
+        let v1_data = v1.get(&root_id).unwrap().data.clone();
+        let v2_data = v2.get(&root_id).unwrap().data.clone();
+        assert!(Rc::ptr_eq(&v1_data, &v2_data));
+
+        // only the list of children that changed is copied
+        let (v3, child_id) = v2.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let (v4, _) = v3.insert(Node::new(3), UnderNode(&child_id)).unwrap();
+        assert!(Rc::ptr_eq(
+            &v3.get(&root_id).unwrap().children,
+            &v4.get(&root_id).unwrap().children
+        ));
+        assert!(!Rc::ptr_eq(
+            &v3.get(&child_id).unwrap().children,
+            &v4.get(&child_id).unwrap().children
+        ));
+    }
+
+    #[test]
+    fn test_many_nodes() {
+        let (mut tree, root_id) = PersistentTree::new().insert(Node::new(0), AsRoot).unwrap();
+        let mut versions = Vec::new();
+        let mut ids = vec![root_id.clone()];
+
+        for i in 1..(WIDTH * WIDTH + 10) as i32 {
+            let parent_id = ids[ids.len() / 2].clone();
+            let (next, id) = tree.insert(Node::new(i), UnderNode(&parent_id)).unwrap();
+            versions.push(tree);
+            tree = next;
+            ids.push(id);
+        }
+
+        for (i, id) in ids.iter().enumerate() {
+            assert_eq!(tree.get(id).unwrap().data(), &(i as i32));
+        }
+
+        // every old version still sees exactly the nodes it had
+        for (count, version) in versions.iter().enumerate() {
+            assert!(version.get(&ids[count]).is_ok());
+            assert!(version.get(&ids[count + 1]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_remove_node() {
+        let (tree, root_id) = PersistentTree::new().insert(Node::new(0), AsRoot).unwrap();
+        let (tree, node_1) = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let (tree, node_2) = tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+        let (tree, node_3) = tree.insert(Node::new(3), UnderNode(&node_1)).unwrap();
+
+        let dropped = tree.remove_node(&node_1, DropChildren).unwrap();
+        assert!(dropped.get(&root_id).unwrap().children().is_empty());
+        assert!(dropped.get(&node_2).is_err());
+        assert!(dropped.get(&node_3).is_err());
+
+        // `tree` has been modified before, so these start branches
+        let lifted = tree.remove_node(&node_1, LiftChildren).unwrap();
+        assert_eq!(
+            lifted.get(&root_id).unwrap().children(),
+            &vec![node_2.clone(), node_3.clone()]
+        );
+
+        let orphaned = tree.remove_node(&node_1, OrphanChildren).unwrap();
+        assert!(orphaned.get(&root_id).unwrap().children().is_empty());
+        assert_eq!(orphaned.get(&node_2).unwrap().parent(), None);
+
+        // the freed slots are reused with a new generation
+        let (reused, new_id) = dropped.insert(Node::new(4), AsRoot).unwrap();
+        assert!(reused.get(&node_1).is_err());
+        assert!(reused.get(&node_2).is_err());
+        assert!(reused.get(&node_3).is_err());
+        assert_eq!(reused.get(&new_id).unwrap().data(), &4);
+
+        // the original version is untouched
+        assert_eq!(tree.get(&node_1).unwrap().children().len(), 2);
+    }
+
+    #[test]
+    fn test_matches_tree() {
+        let mut tree = Tree::new();
+        let persistent = PersistentTree::new();
+
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let (persistent, p_root_id) = persistent.insert(Node::new(0), AsRoot).unwrap();
+
+        let mut ids = vec![root_id];
+        let mut p_ids = vec![p_root_id];
+        for i in 1..12 {
+            let parent = (i as usize - 1) / 3;
+            ids.push(tree.insert(Node::new(i), UnderNode(&ids[parent])).unwrap());
+        }
+        let mut persistent = persistent;
+        for i in 1..12 {
+            let parent = (i as usize - 1) / 3;
+            let (next, id) = persistent
+                .insert(Node::new(i), UnderNode(&p_ids[parent]))
+                .unwrap();
+            persistent = next;
+            p_ids.push(id);
+        }
+        assert_eq!(structure(&tree), structure(&persistent.to_tree()));
+
+        let swaps = [
+            (1, 5, 0),
+            (4, 3, 0),
+            (2, 10, 1),
+            (7, 9, 1),
+            (2, 6, 2),
+            (10, 1, 2),
+        ];
+        for &(first, second, behavior) in swaps.iter() {
+            let behavior = || match behavior {
+                0 => TakeChildren,
+                1 => LeaveChildren,
+                _ => ChildrenOnly,
+            };
+            tree.swap_nodes(&ids[first], &ids[second], behavior())
+                .unwrap();
+            persistent = persistent
+                .swap_nodes(&p_ids[first], &p_ids[second], behavior())
+                .unwrap();
+            assert_eq!(structure(&tree), structure(&persistent.to_tree()));
+        }
+
+        for &(node, parent) in [(3, 8), (0, 11), (6, 2), (11, 4)].iter() {
+            tree.move_node(&ids[node], ToParent(&ids[parent])).unwrap();
+            persistent = persistent
+                .move_node(&p_ids[node], ToParent(&p_ids[parent]))
+                .unwrap();
+            assert_eq!(structure(&tree), structure(&persistent.to_tree()));
+        }
+
+        tree.move_node(&ids[6], ToRoot).unwrap();
+        persistent = persistent.move_node(&p_ids[6], ToRoot).unwrap();
+        assert_eq!(structure(&tree), structure(&persistent.to_tree()));
+
+        tree.remove_node(ids[5].clone(), LiftChildren).unwrap();
+        persistent = persistent.remove_node(&p_ids[5], LiftChildren).unwrap();
+        tree.remove_node(ids[2].clone(), DropChildren).unwrap();
+        persistent = persistent.remove_node(&p_ids[2], DropChildren).unwrap();
+        assert_eq!(structure(&tree), structure(&persistent.to_tree()));
+
+        // both hand out the same free slots
+        let new_id = tree.insert(Node::new(20), AsRoot).unwrap();
+        let (persistent, p_new_id) = persistent.insert(Node::new(20), AsRoot).unwrap();
+        assert_eq!(new_id.index, p_new_id.index);
+        assert_eq!(structure(&tree), structure(&persistent.to_tree()));
+    }
+
+    #[test]
+    fn test_swap_children_only_with_descendant() {
+        let (tree, a) = PersistentTree::new().insert(Node::new(0), AsRoot).unwrap();
+        let (tree, b) = tree.insert(Node::new(1), UnderNode(&a)).unwrap();
+        let (tree, c) = tree.insert(Node::new(2), UnderNode(&b)).unwrap();
+        let (tree, d) = tree.insert(Node::new(3), UnderNode(&c)).unwrap();
+
+        let swapped = tree.swap_nodes(&a, &c, ChildrenOnly).unwrap();
+        assert_eq!(swapped.to_tree().check_integrity(), Ok(()));
+
+        assert_eq!(
+            swapped.get(&a).unwrap().children(),
+            &vec![d.clone(), c.clone()]
+        );
+        assert_eq!(swapped.get(&c).unwrap().children(), &vec![b.clone()]);
+        assert_eq!(swapped.get(&b).unwrap().parent(), Some(&c));
+        assert!(swapped.get(&b).unwrap().children().is_empty());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        tree.remove_node(node_1.clone(), DropChildren).unwrap();
+        let expected = layout(&tree);
+
+        let persistent = PersistentTree::from(tree);
+        assert!(persistent.get(&node_1).is_err());
+        assert_eq!(
+            persistent.get(&root_id).unwrap().children(),
+            &vec![node_2.clone()]
+        );
+
+        let round_trip = persistent.to_tree();
+        assert_eq!(layout(&round_trip), expected);
+        let translate = |id| round_trip.translate_node_id(id).unwrap();
+        assert_eq!(
+            round_trip.get(&translate(&node_2)).unwrap().parent(),
+            Some(&translate(&root_id))
+        );
+
+        // the free slot carries over as well
+        let (persistent, node_3) = persistent.insert(Node::new(3), AsRoot).unwrap();
+        assert_eq!(node_3.index, node_1.index);
+        assert_ne!(node_3, node_1);
+        let tree = persistent.to_tree();
+        assert_eq!(
+            tree.get(&tree.translate_node_id(&root_id).unwrap())
+                .unwrap()
+                .parent(),
+            tree.translate_node_id(&node_3).ok().as_ref()
+        );
+    }
+
+    #[test]
+    fn test_branches() {
+        let (v1, root_id) = PersistentTree::new().insert(Node::new(0), AsRoot).unwrap();
+        let (v2, child_id) = v1.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let (v3, grandchild_id) = v2.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+
+        // v1 and v2 have been modified before, so both of these start a branch
+        let (branch, other_id) = v1.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+        let (other_branch, another_id) = v2.insert(Node::new(4), AsRoot).unwrap();
+
+        // the same slot holds a different node on each branch, but the ids can't be mixed up
+        assert_eq!(other_id.index, child_id.index);
+        assert_ne!(other_id, child_id);
+        assert!(branch.get(&child_id).is_err());
+        assert!(v2.get(&other_id).is_err());
+        assert!(v3.get(&other_id).is_err());
+        assert!(other_branch.get(&grandchild_id).is_err());
+        assert!(v3.get(&another_id).is_err());
+
+        // ids of nodes from before the branch stay valid on it
+        assert_eq!(
+            branch.get(&root_id).unwrap().children(),
+            &vec![other_id.clone()]
+        );
+        assert_eq!(other_branch.root_node_id(), Some(&another_id));
+        assert_eq!(
+            other_branch.get(&root_id).unwrap().parent(),
+            Some(&another_id)
+        );
+        assert_eq!(other_branch.get(&child_id).unwrap().data(), &1);
+
+        // a slot freed and reused on one branch doesn't clash with the other one either
+        let removed = v3.remove_node(&grandchild_id, DropChildren).unwrap();
+        let (reused, reused_id) = removed.insert(Node::new(5), AsRoot).unwrap();
+        let (v4, v4_id) = v3.insert(Node::new(6), UnderNode(&root_id)).unwrap();
+        assert_eq!(reused_id.index, grandchild_id.index);
+        assert!(reused.get(&grandchild_id).is_err());
+        assert!(v3.get(&reused_id).is_err());
+        assert!(reused.get(&v4_id).is_err());
+        assert!(v4.get(&reused_id).is_err());
+        assert_eq!(v4.get(&grandchild_id).unwrap().data(), &2);
+    }
+
+    #[test]
+    fn test_from_tree_after_undo() {
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        tree.enable_journal();
+        let undone_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        tree.undo();
+
+        let persistent = PersistentTree::from(tree);
+        let (persistent, child_id) = persistent
+            .insert(Node::new(2), UnderNode(&root_id))
+            .unwrap();
+        assert_ne!(child_id, undone_id);
+        assert!(persistent.get(&undone_id).is_err());
+    }
+
+    #[test]
+    fn test_to_tree_twice() {
+        let (persistent, root_id) = PersistentTree::new().insert(Node::new(0), AsRoot).unwrap();
+
+        let mut first = persistent.to_tree();
+        let mut second = persistent.to_tree();
+        assert!(first.get(&root_id).is_err());
+
+        let first_root_id = first.translate_node_id(&root_id).unwrap();
+        let second_root_id = second.translate_node_id(&root_id).unwrap();
+        assert_ne!(first_root_id, second_root_id);
+
+        let first_child_id = first
+            .insert(Node::new(1), UnderNode(&first_root_id))
+            .unwrap();
+        let second_child_id = second
+            .insert(Node::new(2), UnderNode(&second_root_id))
+            .unwrap();
+        assert!(first.get(&second_child_id).is_err());
+        assert!(second.get(&first_child_id).is_err());
+
+        // and neither is mistaken for the persistent tree either
+        let persistent = PersistentTree::from(first);
+        assert!(persistent.get(&root_id).is_err());
+        assert_eq!(
+            persistent.get(&first_child_id).unwrap().parent(),
+            Some(&first_root_id)
+        );
+    }
+}
@@ -1,5 +1,18 @@
 use std::cmp::Ordering;
-
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[cfg(feature = "serde_support")]
+use serde::de::Error as DeError;
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Deserializer};
+
+use super::event::EventQueue;
+use super::integrity;
+use super::journal::Journal;
+#[cfg(feature = "serde_support")]
+use super::node_id_seed::detached_tree_id;
+use super::parser;
 use super::snowflake::ProcessUniqueId;
 use super::*;
 
@@ -12,6 +25,12 @@ pub struct TreeBuilder<T> {
     swap_capacity: usize,
 }
 
+impl<T> Default for TreeBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> TreeBuilder<T> {
     ///
     /// Creates a new `TreeBuilder` with the default settings.
@@ -125,16 +144,23 @@ impl<T> TreeBuilder<T> {
             id: tree_id,
             root: None,
             nodes: Vec::with_capacity(self.node_capacity),
+            generations: Vec::with_capacity(self.node_capacity),
             free_ids: Vec::with_capacity(self.swap_capacity),
+            min_generations: Vec::new(),
+            revision: ProcessUniqueId::new(),
+            journal: None,
+            events: None,
         };
 
         if self.root.is_some() {
             let node_id = NodeId {
-                tree_id: tree_id,
+                tree_id,
                 index: 0,
+                generation: 0,
             };
 
             tree.nodes.push(self.root.take());
+            tree.generations.push(0);
             tree.root = Some(node_id);
         }
 
@@ -154,12 +180,28 @@ impl<T> TreeBuilder<T> {
 /// library, but they can happen due to bugs.
 ///
 #[derive(Debug)]
-#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_support", derive(Serialize))]
 pub struct Tree<T> {
-    id: ProcessUniqueId,
-    root: Option<NodeId>,
+    pub(crate) id: ProcessUniqueId,
+    pub(crate) root: Option<NodeId>,
     pub(crate) nodes: Vec<Option<Node<T>>>,
-    free_ids: Vec<NodeId>,
+    // The current generation of each slot in `nodes`.  It is bumped every time a `Node` is taken
+    // out of its slot so that `NodeId`s handed out before can be told apart from new ones.
+    pub(crate) generations: Vec<usize>,
+    pub(crate) free_ids: Vec<NodeId>,
+    // The lowest generation a `Node` inserted into a slot may get.  `undo` can take back the
+    // generation bump of a removal or drop slots altogether, so this makes sure that the
+    // `NodeId`s of undone insertions are never handed out again for different `Node`s.
+    #[cfg_attr(feature = "serde_support", serde(skip))]
+    pub(crate) min_generations: Vec<usize>,
+    // Replaced with a fresh id on every structural change, so that anything derived from the
+    // structure (like an `AncestorIndex`) can tell whether it still matches.
+    #[cfg_attr(feature = "serde_support", serde(skip))]
+    pub(crate) revision: ProcessUniqueId,
+    #[cfg_attr(feature = "serde_support", serde(skip))]
+    pub(crate) journal: Option<Box<Journal<T>>>,
+    #[cfg_attr(feature = "serde_support", serde(skip))]
+    pub(crate) events: Option<EventQueue>,
 }
 
 impl<T> Tree<T> {
@@ -176,6 +218,73 @@ impl<T> Tree<T> {
         TreeBuilder::new().build()
     }
 
+    ///
+    /// Builds a `Tree` from the text layout written by `write_formatted` (or by a
+    /// `TreeFormatter`, in either style).  Every line holds one `Node`, whose data is parsed from
+    /// the rest of the line after the branch characters with the function given.  Blank lines
+    /// are skipped.
+    ///
+    /// Only the depth of each line is checked, not whether the branch characters in front of it
+    /// are the ones that would have been written.  Text with "…N more" lines can't be parsed
+    /// back into the `Tree` it came from.
+    ///
+    /// Returns a `ParseError` with the line and column the first problem was found at.
+    ///
+    /// ```
+    /// use id_tree::*;
+    ///
+    /// let tree: Tree<i32> = Tree::parse_formatted("\
+    /// 0
+    /// ├── 1
+    /// │   └── 2
+    /// └── 3
+    /// ", |label| label.parse()).unwrap();
+    ///
+    /// let mut s = String::new();
+    /// tree.write_formatted(&mut s).unwrap();
+    /// assert_eq!(&s, "0\n├── 1\n│   └── 2\n└── 3\n");
+    ///
+    /// let error = Tree::<i32>::parse_formatted("0\n└── x\n", |label| label.parse()).unwrap_err();
+    /// assert_eq!((error.line(), error.column()), (2, 5));
+    /// ```
+    ///
+    pub fn parse_formatted<E, F>(s: &str, parse_label: F) -> Result<Tree<T>, ParseError<E>>
+    where
+        F: FnMut(&str) -> Result<T, E>,
+    {
+        parser::parse_formatted(s, parse_label)
+    }
+
+    ///
+    /// Builds a `Tree` from a plain indented text layout.  Every line holds one `Node`, whose
+    /// data is parsed from the rest of the line after the leading spaces or tabs with the function
+    /// given.  A line that is indented further than the line before it holds a child of that
+    /// line's `Node`.  Blank lines are skipped.
+    ///
+    /// Returns a `ParseError` with the line and column the first problem was found at.
+    ///
+    /// ```
+    /// use id_tree::*;
+    ///
+    /// let tree: Tree<String> = Tree::parse_indented("\
+    /// root
+    ///   a
+    ///     b
+    ///   c
+    /// ", |label| Ok::<_, ()>(label.to_string())).unwrap();
+    ///
+    /// let root_id = tree.root_node_id().unwrap();
+    /// let labels: Vec<&str> = tree.children(root_id).unwrap().map(|n| n.data().as_str()).collect();
+    /// assert_eq!(labels, vec!["a", "c"]);
+    /// ```
+    ///
+    pub fn parse_indented<E, F>(s: &str, parse_label: F) -> Result<Tree<T>, ParseError<E>>
+    where
+        F: FnMut(&str) -> Result<T, E>,
+    {
+        parser::parse_indented(s, parse_label)
+    }
+
     ///
     /// Returns the number of elements the tree can hold without reallocating.
     ///
@@ -207,13 +316,150 @@ impl<T> Tree<T> {
         }
     }
 
-    fn height_of_node(&self, node: &NodeId) -> usize {
-        let mut h = 0;
-        for n in self.children_ids(node).unwrap() {
-            h = std::cmp::max(h, self.height_of_node(n));
+    fn height_of_node(&self, node_id: &NodeId) -> usize {
+        let mut height = 0;
+        let mut stack = vec![(node_id, 1)];
+        while let Some((node_id, depth)) = stack.pop() {
+            height = std::cmp::max(height, depth);
+            for child_id in self.get_unsafe(node_id).children() {
+                stack.push((child_id, depth + 1));
+            }
+        }
+
+        height
+    }
+
+    ///
+    /// Returns the number of `Node`s in the `Tree`, including orphaned `Node`s.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    /// use id_tree::RemoveBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// assert_eq!(tree.len(), 0);
+    ///
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// assert_eq!(tree.len(), 2);
+    ///
+    /// tree.remove_node(child_id, DropChildren).unwrap();
+    /// assert_eq!(tree.len(), 1);
+    /// ```
+    ///
+    pub fn len(&self) -> usize {
+        self.nodes.len() - self.free_ids.len()
+    }
+
+    ///
+    /// Returns `true` if there are no `Node`s in the `Tree`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// assert!(tree.is_empty());
+    ///
+    /// tree.insert(Node::new(0), AsRoot).unwrap();
+    /// assert!(!tree.is_empty());
+    /// ```
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Returns the number of ancestors of a `Node`.  The root `Node` has a depth of 0.
+    ///
+    /// Returns a `Result` containing the depth or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// assert_eq!(tree.depth(&root_id), Ok(0));
+    /// assert_eq!(tree.depth(&child_id), Ok(1));
+    /// ```
+    ///
+    pub fn depth(&self, node_id: &NodeId) -> Result<usize, NodeIdError> {
+        Ok(self.ancestor_ids(node_id)?.count())
+    }
+
+    ///
+    /// Returns the height of the subtree below a `Node`, counted the same way as `height`: a
+    /// `Node` without children has a height of 1.
+    ///
+    /// Returns a `Result` containing the height or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+    ///
+    /// assert_eq!(tree.subtree_height(&root_id), Ok(3));
+    /// assert_eq!(tree.subtree_height(&child_id), Ok(2));
+    /// ```
+    ///
+    pub fn subtree_height(&self, node_id: &NodeId) -> Result<usize, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::subtree_height: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(self.height_of_node(node_id))
+    }
+
+    ///
+    /// Returns the number of `Node`s in the subtree below a `Node`, including the `Node` itself.
+    ///
+    /// Returns a `Result` containing the size or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+    /// tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// assert_eq!(tree.subtree_size(&root_id), Ok(4));
+    /// assert_eq!(tree.subtree_size(&child_id), Ok(2));
+    /// ```
+    ///
+    pub fn subtree_size(&self, node_id: &NodeId) -> Result<usize, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::subtree_size: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(self.size_of_node(node_id))
+    }
+
+    fn size_of_node(&self, node_id: &NodeId) -> usize {
+        let mut size = 0;
+        let mut stack = vec![node_id];
+        while let Some(node_id) = stack.pop() {
+            size += 1;
+            stack.extend(self.get_unsafe(node_id).children());
         }
 
-        h + 1
+        size
     }
 
     /// Inserts a new `Node` into the `Tree`.  The `InsertBehavior` provided will determine where
@@ -240,19 +486,92 @@ impl<T> Tree<T> {
         node: Node<T>,
         behavior: InsertBehavior,
     ) -> Result<NodeId, NodeIdError> {
+        self.mutate(|tree| tree.insert_node(node, behavior))
+    }
+
+    fn insert_node(
+        &mut self,
+        node: Node<T>,
+        behavior: InsertBehavior,
+    ) -> Result<NodeId, NodeIdError> {
+        self.check_insert_behavior(&behavior)?;
+
         match behavior {
+            InsertBehavior::UnderNode(parent_id) => self.insert_with_parent(node, parent_id),
+            InsertBehavior::UnderNodeAt(parent_id, index) => {
+                self.insert_with_parent_at(node, parent_id, index)
+            }
+            InsertBehavior::BeforeSibling(sibling_id) => {
+                let (parent_id, index) = self.parent_and_position(sibling_id)?;
+                self.insert_with_parent_at(node, &parent_id, index)
+            }
+            InsertBehavior::AfterSibling(sibling_id) => {
+                let (parent_id, index) = self.parent_and_position(sibling_id)?;
+                self.insert_with_parent_at(node, &parent_id, index + 1)
+            }
+            InsertBehavior::AsRoot => Ok(self.set_root(node)),
+            InsertBehavior::AsNewRoot => {
+                let node_id = self.insert_without_parent(node);
+                if self.root.is_none() {
+                    self.root = Some(node_id.clone());
+                }
+                Ok(node_id)
+            }
+        }
+    }
+
+    ///
+    /// Makes sure a `Node` could be inserted according to the given `InsertBehavior` without
+    /// touching the `Tree`.
+    ///
+    fn check_insert_behavior(&self, behavior: &InsertBehavior) -> Result<(), NodeIdError> {
+        match *behavior {
             InsertBehavior::UnderNode(parent_id) => {
                 let (is_valid, error) = self.is_valid_node_id(parent_id);
                 if !is_valid {
                     return Err(error.expect(
                         "Tree::insert: Missing an error value but found an \
-                         invalid NodeId.",
+                             invalid NodeId.",
                     ));
                 }
-                self.insert_with_parent(node, parent_id)
             }
-            InsertBehavior::AsRoot => Ok(self.set_root(node)),
+            InsertBehavior::UnderNodeAt(parent_id, index) => {
+                let (is_valid, error) = self.is_valid_node_id(parent_id);
+                if !is_valid {
+                    return Err(error.expect(
+                        "Tree::insert: Missing an error value but found an \
+                             invalid NodeId.",
+                    ));
+                }
+                if index > self.get_unsafe(parent_id).children().len() {
+                    return Err(NodeIdError::InvalidChildIndex);
+                }
+            }
+            InsertBehavior::BeforeSibling(sibling_id)
+            | InsertBehavior::AfterSibling(sibling_id) => {
+                self.parent_and_position(sibling_id)?;
+            }
+            InsertBehavior::AsRoot | InsertBehavior::AsNewRoot => {}
         }
+        Ok(())
+    }
+
+    ///
+    /// Inserts a `Node` that has no parent and isn't the root `Node`.  This is how a `Forest`
+    /// adds its roots.
+    ///
+    pub(crate) fn insert_orphan(&mut self, node: Node<T>) -> NodeId {
+        self.mutate(|tree| tree.insert_without_parent(node))
+    }
+
+    fn insert_without_parent(&mut self, node: Node<T>) -> NodeId {
+        let node_id = self.insert_new_node(node);
+        self.emit(TreeEvent::Inserted {
+            id: node_id.clone(),
+            parent: None,
+            index: 0,
+        });
+        node_id
     }
 
     ///
@@ -260,9 +579,19 @@ impl<T> Tree<T> {
     ///
     fn set_root(&mut self, new_root: Node<T>) -> NodeId {
         let new_root_id = self.insert_new_node(new_root);
+        self.emit(TreeEvent::Inserted {
+            id: new_root_id.clone(),
+            parent: None,
+            index: 0,
+        });
 
         if let Some(current_root_node_id) = self.root.clone() {
             self.set_as_parent_and_child(&new_root_id, &current_root_node_id);
+            self.emit(TreeEvent::Moved {
+                id: current_root_node_id,
+                old_parent: None,
+                new_parent: Some(new_root_id.clone()),
+            });
         }
 
         self.root = Some(new_root_id.clone());
@@ -278,9 +607,96 @@ impl<T> Tree<T> {
     ) -> Result<NodeId, NodeIdError> {
         let new_child_id = self.insert_new_node(child);
         self.set_as_parent_and_child(parent_id, &new_child_id);
+        self.emit(TreeEvent::Inserted {
+            id: new_child_id.clone(),
+            parent: Some(parent_id.clone()),
+            index: self.get_unsafe(parent_id).children().len() - 1,
+        });
+        Ok(new_child_id)
+    }
+
+    /// Add a new `Node` to the tree as the child of a `Node` specified by the given `NodeId`, at
+    /// the given position amongst its children.
+    ///
+    fn insert_with_parent_at(
+        &mut self,
+        child: Node<T>,
+        parent_id: &NodeId,
+        index: usize,
+    ) -> Result<NodeId, NodeIdError> {
+        let new_child_id = self.insert_new_node(child);
+        self.get_mut_unsafe(parent_id)
+            .insert_child(index, new_child_id.clone());
+        self.set_parent(&new_child_id, Some(parent_id.clone()));
+        self.emit(TreeEvent::Inserted {
+            id: new_child_id.clone(),
+            parent: Some(parent_id.clone()),
+            index,
+        });
         Ok(new_child_id)
     }
 
+    /// Inserts `Node`s that are given in pre-order, along with the `NodeId`s that identified them
+    /// where they came from.  The first `Node` is inserted according to the `InsertBehavior`
+    /// given, all others are attached to the new counterpart of their (old) parent.
+    ///
+    /// Returns a map from the old `NodeId`s to the new ones.
+    ///
+    fn insert_pre_ordered<I>(
+        &mut self,
+        nodes: I,
+        behavior: InsertBehavior,
+    ) -> Result<HashMap<NodeId, NodeId>, NodeIdError>
+    where
+        I: IntoIterator<Item = (NodeId, Node<T>)>,
+    {
+        let mut nodes = nodes.into_iter();
+        let mut id_map = HashMap::with_capacity(nodes.size_hint().0);
+
+        if let Some((old_id, mut node)) = nodes.next() {
+            node.set_parent(None);
+            node.children_mut().clear();
+
+            let new_id = self.insert_node(node, behavior)?;
+            id_map.insert(old_id, new_id);
+        }
+
+        for (old_id, mut node) in nodes {
+            let new_parent_id = node
+                .parent
+                .take()
+                .and_then(|parent_id| id_map.get(&parent_id).cloned())
+                .expect("Tree::insert_pre_ordered: Nodes were not given in pre-order.");
+            node.children_mut().clear();
+
+            let new_id = self.insert_with_parent(node, &new_parent_id)?;
+            id_map.insert(old_id, new_id);
+        }
+
+        Ok(id_map)
+    }
+
+    /// Returns the parent of a `Node` along with the position of the `Node` amongst its
+    /// siblings.
+    ///
+    fn parent_and_position(&self, node_id: &NodeId) -> Result<(NodeId, usize), NodeIdError> {
+        let parent_id = self
+            .get(node_id)?
+            .parent()
+            .cloned()
+            .ok_or(NodeIdError::NodeHasNoParent)?;
+
+        // barring bugs in id_tree, a Node is always amongst the children of its parent
+        let position = self
+            .get_unsafe(&parent_id)
+            .children()
+            .iter()
+            .position(|id| id == node_id)
+            .unwrap();
+
+        Ok((parent_id, position))
+    }
+
     ///
     /// Get an immutable reference to a `Node`.
     ///
@@ -372,19 +788,26 @@ impl<T> Tree<T> {
         node_id: NodeId,
         behavior: RemoveBehavior,
     ) -> Result<Node<T>, NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(&node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::remove_node: Missing an error value but found an \
-                 invalid NodeId.",
-            ));
-        }
+        self.mutate(|tree| {
+            let (is_valid, error) = tree.is_valid_node_id(&node_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::remove_node: Missing an error value but found an \
+                     invalid NodeId.",
+                ));
+            }
 
-        match behavior {
-            RemoveBehavior::DropChildren => self.remove_node_drop_children(node_id),
-            RemoveBehavior::LiftChildren => self.remove_node_lift_children(node_id),
-            RemoveBehavior::OrphanChildren => self.remove_node_orphan_children(node_id),
-        }
+            tree.emit(TreeEvent::Removed {
+                id: node_id.clone(),
+                behavior,
+            });
+
+            match behavior {
+                RemoveBehavior::DropChildren => tree.remove_node_drop_children(node_id),
+                RemoveBehavior::LiftChildren => tree.remove_node_lift_children(node_id),
+                RemoveBehavior::OrphanChildren => tree.remove_node_orphan_children(node_id),
+            }
+        })
     }
 
     ///
@@ -428,58 +851,406 @@ impl<T> Tree<T> {
         Ok(self.remove_node_internal(node_id))
     }
 
-    /// Moves a `Node` in the `Tree` to a new location based upon the `MoveBehavior` provided.
+    /// Remove a `Node` along with all of its descendants from the `Tree` and move them into a
+    /// brand-new `Tree`.  The removed `Node` becomes the root `Node` of the new `Tree` and the
+    /// order of all children is kept.
+    ///
+    /// Returns a `Result` containing the new `Tree` along with a map from the old `NodeId`s to
+    /// the `NodeId`s of the new `Tree`, or a `NodeIdError` if one occurred.
+    ///
+    /// **NOTE:** All of the old `NodeId`s are no longer valid for this `Tree` once this method
+    /// returns.  They should be translated using the returned map instead.
     ///
     /// ```
     /// use id_tree::*;
     /// use id_tree::InsertBehavior::*;
-    /// use id_tree::MoveBehavior::*;
     ///
     /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
     ///
-    /// let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
-    /// let child_id = tree.insert(Node::new(2),  UnderNode(&root_id)).unwrap();
-    /// let grandchild_id = tree.insert(Node::new(3), UnderNode(&child_id)).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let grandchild_id = tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
     ///
-    /// tree.move_node(&grandchild_id, ToRoot).unwrap();
+    /// let (subtree, id_map) = tree.remove_subtree(child_id.clone()).unwrap();
     ///
-    /// assert_eq!(tree.root_node_id(), Some(&grandchild_id));
-    /// # assert!(tree.get(&grandchild_id).unwrap().children().contains(&root_id));
-    /// # assert!(!tree.get(&child_id).unwrap().children().contains(&grandchild_id));
+    /// assert!(tree.get(&child_id).is_err());
+    /// assert_eq!(tree.get(&root_id).unwrap().children().len(), 0);
+    ///
+    /// let new_child_id = &id_map[&child_id];
+    /// let new_grandchild_id = &id_map[&grandchild_id];
+    /// assert_eq!(subtree.root_node_id(), Some(new_child_id));
+    /// assert_eq!(subtree.get(new_grandchild_id).unwrap().data(), &2);
     /// ```
     ///
-    pub fn move_node(
+    pub fn remove_subtree(
         &mut self,
-        node_id: &NodeId,
-        behavior: MoveBehavior,
-    ) -> Result<(), NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::move_node: Missing an error value on finding an \
-                 invalid NodeId.",
-            ));
-        }
+        node_id: NodeId,
+    ) -> Result<(Tree<T>, HashMap<NodeId, NodeId>), NodeIdError> {
+        self.mutate(|tree| {
+            let (is_valid, error) = tree.is_valid_node_id(&node_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::remove_subtree: Missing an error value but found an invalid NodeId.",
+                ));
+            }
 
-        match behavior {
-            MoveBehavior::ToRoot => self.move_node_to_root(node_id),
-            MoveBehavior::ToParent(parent_id) => {
-                let (is_valid, error) = self.is_valid_node_id(parent_id);
-                if !is_valid {
-                    return Err(error.expect(
-                        "Tree::move_node: Missing an error value on finding \
-                         an invalid NodeId.",
-                    ));
-                }
-                self.move_node_to_parent(node_id, parent_id)
+            let subtree_ids: Vec<NodeId> =
+                PreOrderTraversalIds::new(tree, node_id.clone()).collect();
+
+            if let Some(parent_id) = tree.get_unsafe(&node_id).parent().cloned() {
+                tree.detach_from_parent(&parent_id, &node_id);
             }
-        }
+            if tree.root.as_ref() == Some(&node_id) {
+                tree.root = None;
+            }
+            tree.emit(TreeEvent::Removed {
+                id: node_id.clone(),
+                behavior: RemoveBehavior::DropChildren,
+            });
+
+            let mut subtree = TreeBuilder::new()
+                .with_node_capacity(subtree_ids.len())
+                .build();
+
+            let nodes = subtree_ids
+                .into_iter()
+                .map(|id| (id.clone(), tree.take_node(id)));
+            let id_map = subtree.insert_pre_ordered(nodes, InsertBehavior::AsRoot)?;
+
+            Ok((subtree, id_map))
+        })
     }
 
-    /// Moves a `Node` inside a `Tree` to a new parent leaving all children in their place.
+    /// Moves all `Node`s of another `Tree` into this one.  The root `Node` of the other `Tree` is
+    /// inserted according to the `InsertBehavior` provided and all of its descendants keep their
+    /// place (and order) below it.
     ///
-    fn move_node_to_parent(
-        &mut self,
+    /// Returns a `Result` containing a map from the `NodeId`s of the other `Tree` to the new
+    /// `NodeId`s in this `Tree`, or a `GraftError` if one occurred.
+    ///
+    /// **NOTE:** Only the `Node`s that can be reached from the root `Node` of the other `Tree` are
+    /// moved.  Grafting an empty `Tree` does nothing.  The `InsertBehavior` is checked before
+    /// anything is moved, so if it is invalid the other `Tree` is handed back untouched inside
+    /// the `GraftError`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    ///
+    /// let mut other: Tree<i32> = Tree::new();
+    /// let other_root_id = other.insert(Node::new(1), AsRoot).unwrap();
+    /// let other_child_id = other.insert(Node::new(2), UnderNode(&other_root_id)).unwrap();
+    ///
+    /// let id_map = tree.graft(other, UnderNode(&root_id)).unwrap();
+    ///
+    /// let new_root_id = &id_map[&other_root_id];
+    /// let new_child_id = &id_map[&other_child_id];
+    /// assert_eq!(tree.get(&root_id).unwrap().children(), &vec![new_root_id.clone()]);
+    /// assert_eq!(tree.get(new_child_id).unwrap().parent(), Some(new_root_id));
+    /// ```
+    ///
+    pub fn graft(
+        &mut self,
+        mut other: Tree<T>,
+        behavior: InsertBehavior,
+    ) -> Result<HashMap<NodeId, NodeId>, GraftError<T>> {
+        if let Err(error) = self.check_insert_behavior(&behavior) {
+            return Err(GraftError::new(error, other));
+        }
+
+        self.mutate(|tree| {
+            let other_root_id = match other.root.clone() {
+                Some(root_id) => root_id,
+                None => return Ok(HashMap::new()),
+            };
+
+            let other_ids: Vec<NodeId> = PreOrderTraversalIds::new(&other, other_root_id).collect();
+
+            let nodes = other_ids
+                .into_iter()
+                .map(|id| (id.clone(), other.take_node(id)));
+            let id_map = tree
+                .insert_pre_ordered(nodes, behavior)
+                .expect("Tree::graft: Failed to insert Nodes after checking the InsertBehavior.");
+            Ok(id_map)
+        })
+    }
+
+    /// Turns a `Tree<T>` into a `Tree<U>` by applying the given function to the data of every
+    /// `Node`.  The function is given the (old) `NodeId` of each `Node` along with its data.
+    ///
+    /// The new `Tree` has exactly the same shape and slot layout as this one, so `NodeId`s of
+    /// this `Tree` can be turned into `NodeId`s of the new one with `translate_node_id`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mapped: Tree<String> = tree.map(|_, data| data.to_string());
+    ///
+    /// let new_child_id = mapped.translate_node_id(&child_id).unwrap();
+    /// assert_eq!(mapped.get(&new_child_id).unwrap().data(), "2");
+    /// ```
+    ///
+    pub fn map<U, F>(self, mut f: F) -> Tree<U>
+    where
+        F: FnMut(&NodeId, T) -> U,
+    {
+        let old_tree_id = self.id;
+        let generations = self.generations;
+        let min_generations = self.min_generations;
+
+        let nodes = self
+            .nodes
+            .into_iter()
+            .enumerate()
+            .map(|(index, node)| {
+                node.map(|node| {
+                    let old_id = NodeId {
+                        tree_id: old_tree_id,
+                        index,
+                        generation: generations[index],
+                    };
+                    Node {
+                        data: f(&old_id, node.data),
+                        parent: node.parent,
+                        children: node.children,
+                    }
+                })
+            })
+            .collect();
+
+        let mut tree = Tree::with_layout_of(nodes, self.root, generations, self.free_ids);
+        // `translate_node_id` must not accept the `NodeId`s of undone insertions either
+        tree.min_generations = min_generations;
+        tree
+    }
+
+    /// Creates a `Tree<U>` from this one by applying the given function to every `Node`.  This
+    /// `Tree` is left untouched.
+    ///
+    /// The new `Tree` has exactly the same shape and slot layout as this one, so `NodeId`s of
+    /// this `Tree` can be turned into `NodeId`s of the new one with `translate_node_id`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    ///
+    /// let child_counts: Tree<usize> = tree.map_ref(|node| node.children().len());
+    ///
+    /// let new_root_id = child_counts.translate_node_id(&root_id).unwrap();
+    /// assert_eq!(child_counts.get(&new_root_id).unwrap().data(), &1);
+    /// ```
+    ///
+    pub fn map_ref<U, F>(&self, mut f: F) -> Tree<U>
+    where
+        F: FnMut(&Node<T>) -> U,
+    {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                node.as_ref().map(|node| Node {
+                    data: f(node),
+                    parent: node.parent.clone(),
+                    children: node.children.clone(),
+                })
+            })
+            .collect();
+
+        let mut tree = Tree::with_layout_of(
+            nodes,
+            self.root.clone(),
+            self.generations.clone(),
+            self.free_ids.clone(),
+        );
+        tree.min_generations = self.min_generations.clone();
+        tree
+    }
+
+    /// Returns the `NodeId` that refers to the same slot of this `Tree` as the given `NodeId`.
+    /// This is meant to be used with `NodeId`s of a `Tree` that this one was created from with
+    /// `map` or `map_ref`, or that was deserialized into this one.
+    ///
+    /// Returns a `NodeIdError` if the slot is empty or holds a newer `Node` by now
+    /// (`NodeIdNoLongerValid`), or if this `Tree` doesn't have such a slot at all
+    /// (`InvalidNodeIdForTree`).
+    ///
+    /// **NOTE:** There is no way to tell which `Tree` a foreign `NodeId` came from, so this only
+    /// gives meaningful results for the `Tree` that was mapped.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+    ///
+    /// let mapped = tree.map_ref(|node| *node.data() * 2);
+    /// let new_root_id = mapped.translate_node_id(&root_id).unwrap();
+    ///
+    /// assert_eq!(mapped.root_node_id(), Some(&new_root_id));
+    /// ```
+    ///
+    pub fn translate_node_id(&self, node_id: &NodeId) -> Result<NodeId, NodeIdError> {
+        if node_id.index >= self.nodes.len() {
+            return Err(NodeIdError::InvalidNodeIdForTree);
+        }
+
+        if self.nodes[node_id.index].is_none()
+            || self.generations[node_id.index] != node_id.generation
+        {
+            // the Node has been removed, possibly before this Tree was created
+            return Err(NodeIdError::NodeIdNoLongerValid);
+        }
+
+        Ok(NodeId {
+            tree_id: self.id,
+            index: node_id.index,
+            generation: node_id.generation,
+        })
+    }
+
+    /// Builds a `Tree` with a fresh id around the given slots, rebinding every `NodeId` in them
+    /// to the new `Tree`.
+    ///
+    pub(crate) fn with_layout_of(
+        mut nodes: Vec<Option<Node<T>>>,
+        mut root: Option<NodeId>,
+        generations: Vec<usize>,
+        mut free_ids: Vec<NodeId>,
+    ) -> Tree<T> {
+        let tree_id = ProcessUniqueId::new();
+
+        for node in nodes.iter_mut().filter_map(Option::as_mut) {
+            if let Some(ref mut parent_id) = node.parent {
+                parent_id.tree_id = tree_id;
+            }
+            for child_id in node.children.iter_mut() {
+                child_id.tree_id = tree_id;
+            }
+        }
+        if let Some(ref mut root_id) = root {
+            root_id.tree_id = tree_id;
+        }
+        for free_id in free_ids.iter_mut() {
+            free_id.tree_id = tree_id;
+        }
+
+        Tree {
+            id: tree_id,
+            root,
+            nodes,
+            generations,
+            free_ids,
+            min_generations: Vec::new(),
+            revision: ProcessUniqueId::new(),
+            journal: None,
+            events: None,
+        }
+    }
+
+    /// Moves a `Node` in the `Tree` to a new location based upon the `MoveBehavior` provided.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    /// use id_tree::MoveBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    ///
+    /// let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(2),  UnderNode(&root_id)).unwrap();
+    /// let grandchild_id = tree.insert(Node::new(3), UnderNode(&child_id)).unwrap();
+    ///
+    /// tree.move_node(&grandchild_id, ToRoot).unwrap();
+    ///
+    /// assert_eq!(tree.root_node_id(), Some(&grandchild_id));
+    /// # assert!(tree.get(&grandchild_id).unwrap().children().contains(&root_id));
+    /// # assert!(!tree.get(&child_id).unwrap().children().contains(&grandchild_id));
+    /// ```
+    ///
+    pub fn move_node(
+        &mut self,
+        node_id: &NodeId,
+        behavior: MoveBehavior,
+    ) -> Result<(), NodeIdError> {
+        self.mutate(|tree| {
+            let (is_valid, error) = tree.is_valid_node_id(node_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::move_node: Missing an error value on finding an \
+                     invalid NodeId.",
+                ));
+            }
+
+            match behavior {
+                MoveBehavior::ToRoot => tree.move_node_to_root(node_id),
+                MoveBehavior::ToParent(parent_id) => {
+                    let (is_valid, error) = tree.is_valid_node_id(parent_id);
+                    if !is_valid {
+                        return Err(error.expect(
+                            "Tree::move_node: Missing an error value on finding \
+                             an invalid NodeId.",
+                        ));
+                    }
+                    tree.move_node_to_parent(node_id, parent_id)
+                }
+                MoveBehavior::ToNewRoot => {
+                    tree.detach_node(node_id);
+                    if tree.root.is_none() {
+                        tree.root = Some(node_id.clone());
+                    }
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    ///
+    /// Detaches a `Node` from its parent without making it the root `Node`.  This is how a
+    /// `Forest` moves a `Node` to the top level.
+    ///
+    pub(crate) fn orphan_node(&mut self, node_id: &NodeId) -> Result<(), NodeIdError> {
+        self.mutate(|tree| {
+            let (is_valid, error) = tree.is_valid_node_id(node_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::orphan_node: Missing an error value on finding an invalid NodeId.",
+                ));
+            }
+            tree.detach_node(node_id);
+            Ok(())
+        })
+    }
+
+    fn detach_node(&mut self, node_id: &NodeId) {
+        if let Some(parent_id) = self.get_unsafe(node_id).parent().cloned() {
+            self.detach_from_parent(&parent_id, node_id);
+            self.clear_parent(node_id);
+            self.emit(TreeEvent::Moved {
+                id: node_id.clone(),
+                old_parent: Some(parent_id),
+                new_parent: None,
+            });
+        }
+    }
+
+    /// Moves a `Node` inside a `Tree` to a new parent leaving all children in their place.
+    ///
+    fn move_node_to_parent(
+        &mut self,
         node_id: &NodeId,
         parent_id: &NodeId,
     ) -> Result<(), NodeIdError> {
@@ -500,35 +1271,62 @@ impl<T> Tree<T> {
 
                 // set subtree_root as Tree root.
                 self.clear_parent(&subtree_root_id);
-                self.root = Some(subtree_root_id);
+                self.root = Some(subtree_root_id.clone());
+                self.emit(TreeEvent::Moved {
+                    id: subtree_root_id,
+                    old_parent: Some(node_id.clone()),
+                    new_parent: None,
+                });
 
                 self.set_as_parent_and_child(parent_id, node_id);
+                self.emit(TreeEvent::Moved {
+                    id: node_id.clone(),
+                    old_parent: None,
+                    new_parent: Some(parent_id.clone()),
+                });
             } else {
                 // we're moving some other node down the tree.
 
-                if let Some(old_parent) = self.get_unsafe(node_id).parent().cloned() {
+                let old_parent = self.get_unsafe(node_id).parent().cloned();
+                if let Some(ref old_parent) = old_parent {
                     // detach from old parent
-                    self.detach_from_parent(&old_parent, node_id);
+                    self.detach_from_parent(old_parent, node_id);
                     // connect old parent and subtree root
-                    self.set_as_parent_and_child(&old_parent, &subtree_root_id);
+                    self.set_as_parent_and_child(old_parent, &subtree_root_id);
                 } else {
                     // node is orphaned, need to set subtree_root's parent to None (same as node's)
                     self.clear_parent(&subtree_root_id);
                 }
                 // detach subtree_root from node
                 self.detach_from_parent(node_id, &subtree_root_id);
+                self.emit(TreeEvent::Moved {
+                    id: subtree_root_id,
+                    old_parent: Some(node_id.clone()),
+                    new_parent: old_parent.clone(),
+                });
 
                 self.set_as_parent_and_child(parent_id, node_id);
+                self.emit(TreeEvent::Moved {
+                    id: node_id.clone(),
+                    old_parent,
+                    new_parent: Some(parent_id.clone()),
+                });
             }
         } else {
             // this is a move "across" or "up" the tree.
 
             // detach from old parent
-            if let Some(old_parent) = self.get_unsafe(node_id).parent().cloned() {
-                self.detach_from_parent(&old_parent, node_id);
+            let old_parent = self.get_unsafe(node_id).parent().cloned();
+            if let Some(ref old_parent) = old_parent {
+                self.detach_from_parent(old_parent, node_id);
             }
 
             self.set_as_parent_and_child(parent_id, node_id);
+            self.emit(TreeEvent::Moved {
+                id: node_id.clone(),
+                old_parent,
+                new_parent: Some(parent_id.clone()),
+            });
         }
 
         Ok(())
@@ -540,11 +1338,17 @@ impl<T> Tree<T> {
     fn move_node_to_root(&mut self, node_id: &NodeId) -> Result<(), NodeIdError> {
         let old_root = self.root.clone();
 
-        if let Some(parent_id) = self.get_unsafe(node_id).parent().cloned() {
-            self.detach_from_parent(&parent_id, node_id);
+        let old_parent = self.get_unsafe(node_id).parent().cloned();
+        if let Some(ref parent_id) = old_parent {
+            self.detach_from_parent(parent_id, node_id);
         }
         self.clear_parent(node_id);
         self.root = Some(node_id.clone());
+        self.emit(TreeEvent::Moved {
+            id: node_id.clone(),
+            old_parent,
+            new_parent: None,
+        });
 
         if let Some(old_root) = old_root {
             self.move_node_to_parent(&old_root, node_id)?;
@@ -587,18 +1391,23 @@ impl<T> Tree<T> {
     where
         F: FnMut(&Node<T>, &Node<T>) -> Ordering,
     {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::sort_children_by: Missing an error value but found an invalid NodeId.",
-            ));
-        }
+        self.mutate(|tree| {
+            let (is_valid, error) = tree.is_valid_node_id(node_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::sort_children_by: Missing an error value but found an invalid NodeId.",
+                ));
+            }
 
-        let mut children = self.get_mut_unsafe(node_id).take_children();
-        children.sort_by(|a, b| compare(self.get_unsafe(a), self.get_unsafe(b)));
-        self.get_mut_unsafe(node_id).set_children(children);
+            let mut children = tree.get_mut_unsafe(node_id).take_children();
+            children.sort_by(|a, b| compare(tree.get_unsafe(a), tree.get_unsafe(b)));
+            tree.get_mut_unsafe(node_id).set_children(children);
+            tree.emit(TreeEvent::ChildrenReordered {
+                parent: node_id.clone(),
+            });
 
-        Ok(())
+            Ok(())
+        })
     }
 
     ///
@@ -631,18 +1440,23 @@ impl<T> Tree<T> {
     where
         T: Ord,
     {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::sort_children: Missing an error value but found an invalid NodeId.",
-            ));
-        }
+        self.mutate(|tree| {
+            let (is_valid, error) = tree.is_valid_node_id(node_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::sort_children: Missing an error value but found an invalid NodeId.",
+                ));
+            }
 
-        let mut children = self.get_mut_unsafe(node_id).take_children();
-        children.sort_by_key(|a| self.get_unsafe(a).data());
-        self.get_mut_unsafe(node_id).set_children(children);
+            let mut children = tree.get_mut_unsafe(node_id).take_children();
+            children.sort_by_key(|a| tree.get_unsafe(a).data());
+            tree.get_mut_unsafe(node_id).set_children(children);
+            tree.emit(TreeEvent::ChildrenReordered {
+                parent: node_id.clone(),
+            });
 
-        Ok(())
+            Ok(())
+        })
     }
 
     ///
@@ -681,18 +1495,23 @@ impl<T> Tree<T> {
         B: Ord,
         F: FnMut(&Node<T>) -> B,
     {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::sort_children_by_key: Missing an error value but found an invalid NodeId.",
-            ));
-        }
+        self.mutate(|tree| {
+            let (is_valid, error) = tree.is_valid_node_id(node_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::sort_children_by_key: Missing an error value but found an invalid NodeId.",
+                ));
+            }
 
-        let mut children = self.get_mut_unsafe(node_id).take_children();
-        children.sort_by_key(|a| f(self.get_unsafe(a)));
-        self.get_mut_unsafe(node_id).set_children(children);
+            let mut children = tree.get_mut_unsafe(node_id).take_children();
+            children.sort_by_key(|a| f(tree.get_unsafe(a)));
+            tree.get_mut_unsafe(node_id).set_children(children);
+            tree.emit(TreeEvent::ChildrenReordered {
+                parent: node_id.clone(),
+            });
 
-        Result::Ok(())
+            Result::Ok(())
+        })
     }
 
     ///
@@ -729,42 +1548,43 @@ impl<T> Tree<T> {
     /// ```
     ///
     pub fn make_nth_sibling(&mut self, node: &NodeId, pos: usize) -> Result<(), NodeIdError> {
-        let parent = self
-            .get(node)?
-            .parent()
-            .ok_or(NodeIdError::NodeIdNoLongerValid)?
-            .clone();
+        self.mutate(|tree| {
+            let parent = tree
+                .get(node)?
+                .parent()
+                .ok_or(NodeIdError::NodeIdNoLongerValid)?
+                .clone();
 
-        let num_children = self.children_ids(&parent)?.count();
-        let pos = pos.min(num_children - 1);
+            let num_children = tree.children_ids(&parent)?.count();
+            let pos = pos.min(num_children - 1);
 
-        // First determine the current index that the node has
-        // unwrap should not be reachable, since we are searching under node's
-        // own parent, barring bugs in id_tree
-        let mut current_pos = self
-            .children_ids(&parent)?
-            .enumerate()
-            .find_map(|(i, n)| if n == node { Some(i) } else { None })
-            .unwrap();
+            let mut current_pos = tree.index_in_parent(node)?;
 
-        while current_pos != pos {
-            let pos_to_swap = if current_pos < pos {
-                current_pos + 1
-            } else if current_pos > pos {
-                current_pos - 1
-            } else {
-                break;
-            };
-            let node_to_swap = self
-                .children_ids(&parent)?
-                .nth(pos_to_swap)
-                .unwrap()
-                .clone();
-            self.swap_nodes(node, &node_to_swap, SwapBehavior::TakeChildren)?;
-            current_pos = pos_to_swap;
-        }
+            let mut moved = false;
+            while current_pos != pos {
+                let pos_to_swap = if current_pos < pos {
+                    current_pos + 1
+                } else if current_pos > pos {
+                    current_pos - 1
+                } else {
+                    break;
+                };
+                let node_to_swap = tree
+                    .children_ids(&parent)?
+                    .nth(pos_to_swap)
+                    .unwrap()
+                    .clone();
+                tree.swap_nodes(node, &node_to_swap, SwapBehavior::TakeChildren)?;
+                current_pos = pos_to_swap;
+                moved = true;
+            }
 
-        Ok(())
+            if moved {
+                tree.emit(TreeEvent::ChildrenReordered { parent });
+            }
+
+            Ok(())
+        })
     }
 
     /// Puts the node in the first position relative to other sibling nodes.
@@ -799,26 +1619,32 @@ impl<T> Tree<T> {
     /// ```
     ///
     pub fn make_first_sibling(&mut self, node_id: &NodeId) -> Result<bool, NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::make_first_sibling: Missing an error value but found an invalid NodeId.",
-            ));
-        }
+        self.mutate(|tree| {
+            let (is_valid, error) = tree.is_valid_node_id(node_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::make_first_sibling: Missing an error value but found an invalid NodeId.",
+                ));
+            }
 
-        let mut moved = false;
-        if let Some(parent_id) = self.get(node_id)?.parent().cloned() {
-            let parent = self
-                .get_mut(&parent_id)
-                .expect("Tree::make_first_sibling: invalid parent id");
-            let mut position = parent.children.iter().position(|id| id == node_id).unwrap();
-            moved = position > 0;
-            while position > 0 {
-                parent.children.swap(position - 1, position);
-                position -= 1;
+            let mut moved = false;
+            if let Some(parent_id) = tree.get(node_id)?.parent().cloned() {
+                let parent = tree
+                    .get_mut(&parent_id)
+                    .expect("Tree::make_first_sibling: invalid parent id");
+                let mut position = parent.children.iter().position(|id| id == node_id).unwrap();
+                moved = position > 0;
+                while position > 0 {
+                    parent.children.swap(position - 1, position);
+                    position -= 1;
+                }
             }
-        }
-        Ok(moved)
+            if moved {
+                let parent = tree.get_unsafe(node_id).parent().cloned().unwrap();
+                tree.emit(TreeEvent::ChildrenReordered { parent });
+            }
+            Ok(moved)
+        })
     }
 
     /// Puts the node in the last position relative to other sibling nodes.
@@ -853,27 +1679,33 @@ impl<T> Tree<T> {
     /// ```
     ///
     pub fn make_last_sibling(&mut self, node_id: &NodeId) -> Result<bool, NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::make_last_sibling: Missing an error value but found an invalid NodeId.",
-            ));
-        }
+        self.mutate(|tree| {
+            let (is_valid, error) = tree.is_valid_node_id(node_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::make_last_sibling: Missing an error value but found an invalid NodeId.",
+                ));
+            }
 
-        let mut moved = false;
-        if let Some(parent_id) = self.get(node_id)?.parent().cloned() {
-            let parent = self
-                .get_mut(&parent_id)
-                .expect("Tree::make_last_sibling: invalid parent id");
-            let mut position = parent.children.iter().position(|id| id == node_id).unwrap();
-            let last_position = parent.children.len() - 1;
-            moved = position < last_position;
-            while position < last_position {
-                parent.children.swap(position + 1, position);
-                position += 1;
+            let mut moved = false;
+            if let Some(parent_id) = tree.get(node_id)?.parent().cloned() {
+                let parent = tree
+                    .get_mut(&parent_id)
+                    .expect("Tree::make_last_sibling: invalid parent id");
+                let mut position = parent.children.iter().position(|id| id == node_id).unwrap();
+                let last_position = parent.children.len() - 1;
+                moved = position < last_position;
+                while position < last_position {
+                    parent.children.swap(position + 1, position);
+                    position += 1;
+                }
             }
-        }
-        Ok(moved)
+            if moved {
+                let parent = tree.get_unsafe(node_id).parent().cloned().unwrap();
+                tree.emit(TreeEvent::ChildrenReordered { parent });
+            }
+            Ok(moved)
+        })
     }
 
     /// Swap `Node`s in the `Tree` based upon the `SwapBehavior` provided.
@@ -910,23 +1742,34 @@ impl<T> Tree<T> {
         second_id: &NodeId,
         behavior: SwapBehavior,
     ) -> Result<(), NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(first_id);
-        if !is_valid {
-            return Err(error
-                .expect("Tree::swap_nodes: Missing an error value but found an invalid NodeId."));
-        }
+        self.mutate(|tree| {
+            let (is_valid, error) = tree.is_valid_node_id(first_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::swap_nodes: Missing an error value but found an invalid NodeId.",
+                ));
+            }
 
-        let (is_valid, error) = self.is_valid_node_id(second_id);
-        if !is_valid {
-            return Err(error
-                .expect("Tree::swap_nodes: Missing an error value but found an invalid NodeId."));
-        }
+            let (is_valid, error) = tree.is_valid_node_id(second_id);
+            if !is_valid {
+                return Err(error.expect(
+                    "Tree::swap_nodes: Missing an error value but found an invalid NodeId.",
+                ));
+            }
 
-        match behavior {
-            SwapBehavior::TakeChildren => self.swap_nodes_take_children(first_id, second_id),
-            SwapBehavior::LeaveChildren => self.swap_nodes_leave_children(first_id, second_id),
-            SwapBehavior::ChildrenOnly => self.swap_nodes_children_only(first_id, second_id),
-        }
+            match behavior {
+                SwapBehavior::TakeChildren => tree.swap_nodes_take_children(first_id, second_id),
+                SwapBehavior::LeaveChildren => tree.swap_nodes_leave_children(first_id, second_id),
+                SwapBehavior::ChildrenOnly => tree.swap_nodes_children_only(first_id, second_id),
+            }?;
+
+            tree.emit(TreeEvent::Swapped {
+                first: first_id.clone(),
+                second: second_id.clone(),
+                behavior,
+            });
+            Ok(())
+        })
     }
 
     /// Swaps two `Node`s including their children given their `NodeId`s.
@@ -963,8 +1806,8 @@ impl<T> Tree<T> {
 
             self.detach_from_parent(&lower_parent_id, lower_id);
 
-            if upper_parent_id.is_some() {
-                self.get_mut_unsafe(upper_parent_id.as_ref().unwrap())
+            if let Some(ref upper_parent_id) = upper_parent_id {
+                self.get_mut_unsafe(upper_parent_id)
                     .replace_child(upper_id.clone(), lower_id.clone());
             } else if self.root.as_ref() == Some(upper_id) {
                 self.root = Some(lower_id.clone());
@@ -1184,6 +2027,10 @@ impl<T> Tree<T> {
             if upper_id == &lower_parent {
                 // direct child
                 upper_children.retain(|id| id != lower_id);
+            } else {
+                self.get_mut_unsafe(&lower_parent)
+                    .children_mut()
+                    .retain(|id| id != lower_id);
             }
 
             //swap children of these nodes
@@ -1232,65 +2079,354 @@ impl<T> Tree<T> {
     }
 
     ///
-    /// Returns an `Ancestors` iterator (or a `NodeIdError` if one occurred).
+    /// Returns an `OrphanIds` iterator over the `Node`s that have no parent but aren't the root
+    /// `Node`.
     ///
-    /// Allows iteration over the ancestor `Node`s of a given `NodeId` directly instead of having
-    /// to call `tree.get(...)` with a `NodeId` each time.
+    /// `Node`s end up like this when their parent is removed with
+    /// `RemoveBehavior::OrphanChildren` (or with `RemoveBehavior::LiftChildren` while it is the
+    /// root `Node`).  They stay in the `Tree` until they are removed or `drop_orphans` is called.
     ///
     /// ```
     /// use id_tree::*;
     /// use id_tree::InsertBehavior::*;
+    /// use id_tree::RemoveBehavior::*;
     ///
     /// let mut tree: Tree<i32> = Tree::new();
     /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
-    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let grandchild_id = tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
     ///
-    /// let mut ancestors = tree.ancestors(&node_1).unwrap();
+    /// assert_eq!(tree.orphan_ids().next(), None);
     ///
-    /// assert_eq!(ancestors.next().unwrap().data(), &0);
-    /// assert!(ancestors.next().is_none());
+    /// tree.remove_node(child_id, OrphanChildren).unwrap();
+    ///
+    /// let orphan_ids: Vec<NodeId> = tree.orphan_ids().collect();
+    /// assert_eq!(orphan_ids, vec![grandchild_id]);
     /// ```
     ///
-    pub fn ancestors(&self, node_id: &NodeId) -> Result<Ancestors<T>, NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error
-                .expect("Tree::ancestors: Missing an error value but found an invalid NodeId."));
-        }
-
-        Ok(Ancestors::new(self, node_id.clone()))
+    pub fn orphan_ids(&self) -> OrphanIds<'_, T> {
+        OrphanIds::new(self)
     }
 
     ///
-    /// Returns an `AncestorIds` iterator (or a `NodeIdError` if one occurred).
-    ///
-    /// Allows iteration over the ancestor `NodeId`s of a given `NodeId`.
+    /// Returns a `ForestRoots` iterator over the root `Node` (if there is one) followed by all
+    /// orphaned `Node`s.  Together, the subtrees below these `Node`s hold every `Node` of the
+    /// `Tree`.
     ///
     /// ```
     /// use id_tree::*;
     /// use id_tree::InsertBehavior::*;
+    /// use id_tree::RemoveBehavior::*;
     ///
     /// let mut tree: Tree<i32> = Tree::new();
     /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
-    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let first_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let second_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
     ///
-    /// let mut ancestor_ids = tree.ancestor_ids(&node_1).unwrap();
+    /// tree.remove_node(root_id, LiftChildren).unwrap();
+    /// assert_eq!(tree.root_node_id(), None);
     ///
-    /// assert_eq!(ancestor_ids.next().unwrap(), &root_id);
-    /// assert!(ancestor_ids.next().is_none());
+    /// let roots: Vec<NodeId> = tree.forest_roots().collect();
+    /// assert_eq!(roots, vec![first_id, second_id]);
     /// ```
     ///
-    pub fn ancestor_ids(&self, node_id: &NodeId) -> Result<AncestorIds<T>, NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::ancestor_ids: Missing an error value but found an invalid NodeId.",
-            ));
-        }
+    pub fn forest_roots(&self) -> ForestRoots<'_, T> {
+        ForestRoots::new(self)
+    }
+
+    ///
+    /// Returns the number of `Node`s that can be reached from the root `Node`, including the root
+    /// `Node` itself.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    /// use id_tree::RemoveBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+    /// tree.insert(Node::new(3), UnderNode(&child_id)).unwrap();
+    ///
+    /// tree.remove_node(child_id, OrphanChildren).unwrap();
+    ///
+    /// assert_eq!(tree.reachable_count(), 1);
+    /// assert_eq!(tree.orphaned_count(), 2);
+    /// ```
+    ///
+    pub fn reachable_count(&self) -> usize {
+        self.root
+            .as_ref()
+            .map_or(0, |root_id| self.size_of_node(root_id))
+    }
+
+    ///
+    /// Returns the number of `Node`s that can't be reached from the root `Node`, that is the
+    /// orphaned `Node`s and all of their descendants.
+    ///
+    pub fn orphaned_count(&self) -> usize {
+        self.len() - self.reachable_count()
+    }
+
+    ///
+    /// Removes all orphaned `Node`s along with their descendants from the `Tree`.
+    ///
+    /// Returns the number of `Node`s that were removed.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    /// use id_tree::RemoveBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let grandchild_id = tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+    /// tree.insert(Node::new(3), UnderNode(&grandchild_id)).unwrap();
+    ///
+    /// tree.remove_node(child_id, OrphanChildren).unwrap();
+    ///
+    /// assert_eq!(tree.drop_orphans(), 2);
+    /// assert!(tree.get(&grandchild_id).is_err());
+    /// assert_eq!(tree.orphan_ids().next(), None);
+    /// ```
+    ///
+    pub fn drop_orphans(&mut self) -> usize {
+        self.mutate(|tree| {
+            let orphan_ids: Vec<NodeId> = tree.orphan_ids().collect();
+
+            let mut dropped = 0;
+            for orphan_id in orphan_ids {
+                tree.emit(TreeEvent::Removed {
+                    id: orphan_id.clone(),
+                    behavior: RemoveBehavior::DropChildren,
+                });
+
+                let subtree_ids: Vec<NodeId> = PreOrderTraversalIds::new(tree, orphan_id).collect();
+                dropped += subtree_ids.len();
+                for node_id in subtree_ids {
+                    tree.take_node(node_id);
+                }
+            }
+            dropped
+        })
+    }
+
+    ///
+    /// Returns an `Ancestors` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over the ancestor `Node`s of a given `NodeId` directly instead of having
+    /// to call `tree.get(...)` with a `NodeId` each time.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut ancestors = tree.ancestors(&node_1).unwrap();
+    ///
+    /// assert_eq!(ancestors.next().unwrap().data(), &0);
+    /// assert!(ancestors.next().is_none());
+    /// ```
+    ///
+    pub fn ancestors(&self, node_id: &NodeId) -> Result<Ancestors<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error
+                .expect("Tree::ancestors: Missing an error value but found an invalid NodeId."));
+        }
+
+        Ok(Ancestors::new(self, node_id.clone()))
+    }
+
+    ///
+    /// Returns an `AncestorIds` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over the ancestor `NodeId`s of a given `NodeId`.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut ancestor_ids = tree.ancestor_ids(&node_1).unwrap();
+    ///
+    /// assert_eq!(ancestor_ids.next().unwrap(), &root_id);
+    /// assert!(ancestor_ids.next().is_none());
+    /// ```
+    ///
+    pub fn ancestor_ids(&self, node_id: &NodeId) -> Result<AncestorIds<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::ancestor_ids: Missing an error value but found an invalid NodeId.",
+            ));
+        }
 
         Ok(AncestorIds::new(self, node_id.clone()))
     }
 
+    ///
+    /// Returns `true` if the `Node` identified by `ancestor_id` is an ancestor of the `Node`
+    /// identified by `node_id`.  A `Node` is not an ancestor of itself.
+    ///
+    /// Returns a `Result` containing the answer or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+    ///
+    /// assert_eq!(tree.is_ancestor_of(&root_id, &node_2), Ok(true));
+    /// assert_eq!(tree.is_ancestor_of(&node_2, &root_id), Ok(false));
+    /// assert_eq!(tree.is_ancestor_of(&node_1, &node_1), Ok(false));
+    /// ```
+    ///
+    pub fn is_ancestor_of(
+        &self,
+        ancestor_id: &NodeId,
+        node_id: &NodeId,
+    ) -> Result<bool, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(ancestor_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::is_ancestor_of: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(self.ancestor_ids(node_id)?.any(|id| id == ancestor_id))
+    }
+
+    ///
+    /// Returns the `NodeId` of the lowest `Node` that both given `Node`s are descendants of.  A
+    /// `Node` counts as a descendant of itself here, so if one `Node` is an ancestor of the other
+    /// (or both are the same `Node`), that `Node` is returned.
+    ///
+    /// Returns `None` if the `Node`s don't share an ancestor, which can only happen if at least
+    /// one of them is orphaned.
+    ///
+    /// Returns a `Result` containing the `NodeId` or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&node_1)).unwrap();
+    ///
+    /// assert_eq!(tree.lowest_common_ancestor(&node_2, &node_3), Ok(Some(node_1.clone())));
+    /// assert_eq!(tree.lowest_common_ancestor(&node_1, &node_3), Ok(Some(node_1)));
+    /// ```
+    ///
+    pub fn lowest_common_ancestor(
+        &self,
+        a: &NodeId,
+        b: &NodeId,
+    ) -> Result<Option<NodeId>, NodeIdError> {
+        let a_depth = self.depth(a)?;
+        let b_depth = self.depth(b)?;
+
+        // bring both `Node`s to the same depth, then go up in lockstep until they meet
+        let a_ids = std::iter::once(a)
+            .chain(self.ancestor_ids(a)?)
+            .skip(a_depth.saturating_sub(b_depth));
+        let b_ids = std::iter::once(b)
+            .chain(self.ancestor_ids(b)?)
+            .skip(b_depth.saturating_sub(a_depth));
+
+        Ok(a_ids
+            .zip(b_ids)
+            .find(|&(a_id, b_id)| a_id == b_id)
+            .map(|(lca_id, _)| lca_id.clone()))
+    }
+
+    ///
+    /// Returns a `PathIds` iterator over the path from the `Node` identified by `from` to the
+    /// `Node` identified by `to`.
+    ///
+    /// The path goes up from `from` to the lowest common ancestor of both `Node`s and then down to
+    /// `to`, both ends included.  Returns `None` if the `Node`s don't share an ancestor (see
+    /// `lowest_common_ancestor`).
+    ///
+    /// Returns a `Result` containing the iterator or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let path: Vec<NodeId> = tree.path(&node_2, &node_3).unwrap().unwrap().collect();
+    ///
+    /// assert_eq!(path, vec![node_2, node_1, root_id, node_3]);
+    /// ```
+    ///
+    pub fn path(&self, from: &NodeId, to: &NodeId) -> Result<Option<PathIds<'_, T>>, NodeIdError> {
+        let lca_id = match self.lowest_common_ancestor(from, to)? {
+            Some(lca_id) => lca_id,
+            None => return Ok(None),
+        };
+
+        let down = std::iter::once(to)
+            .chain(self.ancestor_ids(to)?)
+            .take_while(|id| **id != lca_id)
+            .cloned()
+            .collect();
+
+        Ok(Some(PathIds::new(self, from.clone(), lca_id, down)))
+    }
+
+    ///
+    /// Returns a `AncestorsMut` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows changing the data of the ancestor `Node`s of a given `NodeId` while iterating over
+    /// them.  Each item is the `NodeId` of a `Node` along with a mutable reference to its data.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// for (_, data) in tree.ancestors_mut(&node_1).unwrap() {
+    ///     *data += 10;
+    /// }
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().data(), &10);
+    /// assert_eq!(tree.get(&node_1).unwrap().data(), &1);
+    /// ```
+    ///
+    pub fn ancestors_mut(&mut self, node_id: &NodeId) -> Result<AncestorsMut<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::ancestors_mut: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(AncestorsMut::new(self, node_id.clone()))
+    }
+
     ///
     /// Returns a `Children` iterator (or a `NodeIdError` if one occurred).
     ///
@@ -1311,7 +2447,7 @@ impl<T> Tree<T> {
     /// assert!(children.next().is_none());
     /// ```
     ///
-    pub fn children(&self, node_id: &NodeId) -> Result<Children<T>, NodeIdError> {
+    pub fn children(&self, node_id: &NodeId) -> Result<Children<'_, T>, NodeIdError> {
         let (is_valid, error) = self.is_valid_node_id(node_id);
         if !is_valid {
             return Err(
@@ -1341,7 +2477,7 @@ impl<T> Tree<T> {
     /// assert!(children_ids.next().is_none());
     /// ```
     ///
-    pub fn children_ids(&self, node_id: &NodeId) -> Result<ChildrenIds, NodeIdError> {
+    pub fn children_ids(&self, node_id: &NodeId) -> Result<ChildrenIds<'_>, NodeIdError> {
         let (is_valid, error) = self.is_valid_node_id(node_id);
         if !is_valid {
             return Err(error.expect(
@@ -1352,10 +2488,11 @@ impl<T> Tree<T> {
         Ok(ChildrenIds::new(self, node_id.clone()))
     }
 
-    /// Returns a `PreOrderTraversal` iterator (or a `NodeIdError` if one occurred).
     ///
-    /// Allows iteration over all of the `Node`s in the sub-tree below a given `Node`.  This
-    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    /// Returns the position of a `Node` amongst the children of its parent.
+    ///
+    /// Returns a `Result` containing the position or a `NodeIdError` if one occurred.
+    /// `NodeIdError::NodeHasNoParent` is returned for the root `Node` and for orphaned `Node`s.
     ///
     /// ```
     /// use id_tree::*;
@@ -1363,135 +2500,1115 @@ impl<T> Tree<T> {
     ///
     /// let mut tree: Tree<i32> = Tree::new();
     /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
-    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
     ///
-    /// let mut nodes = tree.traverse_pre_order(&root_id).unwrap();
+    /// assert_eq!(tree.index_in_parent(&node_2), Ok(1));
+    /// assert_eq!(tree.index_in_parent(&root_id), Err(NodeIdError::NodeHasNoParent));
+    /// ```
+    ///
+    pub fn index_in_parent(&self, node_id: &NodeId) -> Result<usize, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::index_in_parent: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        self.siblings_around(node_id)
+            .map(|(_, index)| index)
+            .ok_or(NodeIdError::NodeHasNoParent)
+    }
+
+    ///
+    /// Returns the sibling that comes right after a `Node`, or `None` if it is the last child of
+    /// its parent or doesn't have a parent.
+    ///
+    /// Returns a `Result` containing the sibling or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// assert_eq!(tree.next_sibling(&node_1).unwrap().unwrap().data(), &2);
+    /// assert!(tree.next_sibling(&node_3).unwrap().is_none());
+    /// ```
+    ///
+    pub fn next_sibling(&self, node_id: &NodeId) -> Result<Option<&Node<T>>, NodeIdError> {
+        Ok(self.next_sibling_id(node_id)?.map(|id| self.get_unsafe(id)))
+    }
+
+    ///
+    /// Returns the `NodeId` of the sibling that comes right after a `Node`, or `None` if it is
+    /// the last child of its parent or doesn't have a parent.
+    ///
+    /// Returns a `Result` containing the `NodeId` or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// assert_eq!(tree.next_sibling_id(&node_1), Ok(Some(&node_2)));
+    /// assert_eq!(tree.next_sibling_id(&node_3), Ok(None));
+    /// ```
+    ///
+    pub fn next_sibling_id(&self, node_id: &NodeId) -> Result<Option<&NodeId>, NodeIdError> {
+        Ok(self.following_sibling_ids(node_id)?.next())
+    }
+
+    ///
+    /// Returns the sibling that comes right before a `Node`, or `None` if it is the first child
+    /// of its parent or doesn't have a parent.
+    ///
+    /// Returns a `Result` containing the sibling or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// assert_eq!(tree.prev_sibling(&node_3).unwrap().unwrap().data(), &2);
+    /// assert!(tree.prev_sibling(&node_1).unwrap().is_none());
+    /// ```
+    ///
+    pub fn prev_sibling(&self, node_id: &NodeId) -> Result<Option<&Node<T>>, NodeIdError> {
+        Ok(self.prev_sibling_id(node_id)?.map(|id| self.get_unsafe(id)))
+    }
+
+    ///
+    /// Returns the `NodeId` of the sibling that comes right before a `Node`, or `None` if it is
+    /// the first child of its parent or doesn't have a parent.
+    ///
+    /// Returns a `Result` containing the `NodeId` or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// assert_eq!(tree.prev_sibling_id(&node_3), Ok(Some(&node_2)));
+    /// assert_eq!(tree.prev_sibling_id(&node_1), Ok(None));
+    /// ```
+    ///
+    pub fn prev_sibling_id(&self, node_id: &NodeId) -> Result<Option<&NodeId>, NodeIdError> {
+        Ok(self.preceding_sibling_ids(node_id)?.next())
+    }
+
+    ///
+    /// Returns a `Siblings` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over the other children of the parent of a given `NodeId`, in order.  The
+    /// root `Node` and orphaned `Node`s don't have any siblings.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut siblings = tree.siblings(&node_2).unwrap();
+    ///
+    /// assert_eq!(siblings.next().unwrap().data(), &1);
+    /// assert_eq!(siblings.next().unwrap().data(), &3);
+    /// assert!(siblings.next().is_none());
+    /// ```
+    ///
+    pub fn siblings(&self, node_id: &NodeId) -> Result<Siblings<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(
+                error.expect("Tree::siblings: Missing an error value but found an invalid NodeId.")
+            );
+        }
+
+        let (preceding, following) = self.split_siblings(node_id);
+        Ok(Siblings::new(self, preceding, following))
+    }
+
+    ///
+    /// Returns a `SiblingIds` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over the `NodeId`s of the other children of the parent of a given
+    /// `NodeId`, in order.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut sibling_ids = tree.sibling_ids(&node_2).unwrap();
+    ///
+    /// assert_eq!(sibling_ids.next(), Some(&node_1));
+    /// assert_eq!(sibling_ids.next(), Some(&node_3));
+    /// assert!(sibling_ids.next().is_none());
+    /// ```
+    ///
+    pub fn sibling_ids(&self, node_id: &NodeId) -> Result<SiblingIds<'_>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error
+                .expect("Tree::sibling_ids: Missing an error value but found an invalid NodeId."));
+        }
+
+        let (preceding, following) = self.split_siblings(node_id);
+        Ok(SiblingIds::new(preceding, following))
+    }
+
+    ///
+    /// Returns a `FollowingSiblings` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over the siblings that come after a given `NodeId`, starting with the
+    /// next one.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut following = tree.following_siblings(&node_1).unwrap();
+    ///
+    /// assert_eq!(following.next().unwrap().data(), &2);
+    /// assert_eq!(following.next().unwrap().data(), &3);
+    /// assert!(following.next().is_none());
+    /// ```
+    ///
+    pub fn following_siblings(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<FollowingSiblings<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::following_siblings: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        let (_, following) = self.split_siblings(node_id);
+        Ok(FollowingSiblings::new(self, following))
+    }
+
+    ///
+    /// Returns a `FollowingSiblingIds` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over the `NodeId`s of the siblings that come after a given `NodeId`,
+    /// starting with the next one.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut following_ids = tree.following_sibling_ids(&node_2).unwrap();
+    ///
+    /// assert_eq!(following_ids.next(), Some(&node_3));
+    /// assert!(following_ids.next().is_none());
+    /// ```
+    ///
+    pub fn following_sibling_ids(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<FollowingSiblingIds<'_>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::following_sibling_ids: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        let (_, following) = self.split_siblings(node_id);
+        Ok(FollowingSiblingIds::new(following))
+    }
+
+    ///
+    /// Returns a `PrecedingSiblings` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over the siblings that come before a given `NodeId`, starting with the
+    /// previous one (so in reverse order).
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut preceding = tree.preceding_siblings(&node_3).unwrap();
+    ///
+    /// assert_eq!(preceding.next().unwrap().data(), &2);
+    /// assert_eq!(preceding.next().unwrap().data(), &1);
+    /// assert!(preceding.next().is_none());
+    /// ```
+    ///
+    pub fn preceding_siblings(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<PrecedingSiblings<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::preceding_siblings: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        let (preceding, _) = self.split_siblings(node_id);
+        Ok(PrecedingSiblings::new(self, preceding))
+    }
+
+    ///
+    /// Returns a `PrecedingSiblingIds` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over the `NodeId`s of the siblings that come before a given `NodeId`,
+    /// starting with the previous one (so in reverse order).
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let node_2 = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    /// let node_3 = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut preceding_ids = tree.preceding_sibling_ids(&node_2).unwrap();
+    ///
+    /// assert_eq!(preceding_ids.next(), Some(&node_1));
+    /// assert!(preceding_ids.next().is_none());
+    /// ```
+    ///
+    pub fn preceding_sibling_ids(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<PrecedingSiblingIds<'_>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::preceding_sibling_ids: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        let (preceding, _) = self.split_siblings(node_id);
+        Ok(PrecedingSiblingIds::new(preceding))
+    }
+
+    ///
+    /// Returns a `ChildrenMut` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows changing the data of the child `Node`s of a given `NodeId` while iterating over
+    /// them.  Each item is the `NodeId` of a `Node` along with a mutable reference to its data.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// for (_, data) in tree.children_mut(&root_id).unwrap() {
+    ///     *data += 10;
+    /// }
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().data(), &0);
+    /// assert_eq!(tree.get(&node_1).unwrap().data(), &11);
+    /// ```
+    ///
+    pub fn children_mut(&mut self, node_id: &NodeId) -> Result<ChildrenMut<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::children_mut: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(ChildrenMut::new(self, node_id.clone()))
+    }
+
+    /// Returns a `PreOrderTraversal` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over all of the `Node`s in the sub-tree below a given `Node`.  This
+    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut nodes = tree.traverse_pre_order(&root_id).unwrap();
+    ///
+    /// assert_eq!(nodes.next().unwrap().data(), &0);
+    /// assert_eq!(nodes.next().unwrap().data(), &1);
+    /// assert!(nodes.next().is_none());
+    /// ```
+    ///
+    pub fn traverse_pre_order(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<PreOrderTraversal<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::traverse_pre_order: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(PreOrderTraversal::new(self, node_id.clone()))
+    }
+
+    /// Returns a `PreOrderTraversalIds` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over all of the `NodeId`s in the sub-tree below a given `NodeId`.  This
+    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut nodes = tree.traverse_pre_order_ids(&root_id).unwrap();
+    ///
+    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &0);
+    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &1);
+    /// assert!(nodes.next().is_none());
+    /// ```
+    ///
+    pub fn traverse_pre_order_ids(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<PreOrderTraversalIds<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::traverse_pre_order_ids: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(PreOrderTraversalIds::new(self, node_id.clone()))
+    }
+
+    ///
+    /// Returns a `PreOrderTraversalMut` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows changing the data of all of the `Node`s in the sub-tree below a given `NodeId`
+    /// (including that `Node` itself) while iterating over them in Pre-Order.  Each item is the
+    /// `NodeId` of a `Node` along with a mutable reference to its data.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// for (_, data) in tree.traverse_pre_order_mut(&root_id).unwrap() {
+    ///     *data += 10;
+    /// }
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().data(), &10);
+    /// assert_eq!(tree.get(&node_1).unwrap().data(), &11);
+    /// ```
+    ///
+    pub fn traverse_pre_order_mut(
+        &mut self,
+        node_id: &NodeId,
+    ) -> Result<PreOrderTraversalMut<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::traverse_pre_order_mut: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(PreOrderTraversalMut::new(self, node_id.clone()))
+    }
+
+    /// Returns a `PostOrderTraversal` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over all of the `Node`s in the sub-tree below a given `Node`.  This
+    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut nodes = tree.traverse_post_order(&root_id).unwrap();
+    ///
+    /// assert_eq!(nodes.next().unwrap().data(), &1);
+    /// assert_eq!(nodes.next().unwrap().data(), &0);
+    /// assert!(nodes.next().is_none());
+    /// ```
+    ///
+    pub fn traverse_post_order(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<PostOrderTraversal<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::traverse_post_order: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(PostOrderTraversal::new(self, node_id.clone()))
+    }
+
+    /// Returns a `PostOrderTraversalIds` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over all of the `NodeId`s in the sub-tree below a given `NodeId`.  This
+    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut nodes = tree.traverse_post_order_ids(&root_id).unwrap();
+    ///
+    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &1);
+    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &0);
+    /// assert!(nodes.next().is_none());
+    /// ```
+    ///
+    pub fn traverse_post_order_ids(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<PostOrderTraversalIds<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::traverse_post_order_ids: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(PostOrderTraversalIds::new(self, node_id.clone()))
+    }
+
+    ///
+    /// Returns a `PostOrderTraversalMut` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows changing the data of all of the `Node`s in the sub-tree below a given `NodeId`
+    /// (including that `Node` itself) while iterating over them in Post-Order.  Each item is the
+    /// `NodeId` of a `Node` along with a mutable reference to its data.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// for (_, data) in tree.traverse_post_order_mut(&root_id).unwrap() {
+    ///     *data += 10;
+    /// }
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().data(), &10);
+    /// assert_eq!(tree.get(&node_1).unwrap().data(), &11);
+    /// ```
+    ///
+    pub fn traverse_post_order_mut(
+        &mut self,
+        node_id: &NodeId,
+    ) -> Result<PostOrderTraversalMut<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::traverse_post_order_mut: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(PostOrderTraversalMut::new(self, node_id.clone()))
+    }
+
+    /// Folds the sub-tree below a given `NodeId` bottom-up (or returns a `NodeIdError` if one
+    /// occurred).
+    ///
+    /// The function given is called once for every `Node` in the sub-tree, along with the
+    /// results for that `Node`'s children (in child order).  The result for the sub-tree "root"
+    /// specified by the `NodeId` given is returned.
+    ///
+    /// This does not recurse and looks at every `Node` only once, so it can be used on arbitrarily
+    /// deep or wide `Tree`s.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+    /// tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let subtree_size = tree
+    ///     .fold_subtree(&root_id, |_, children: Vec<usize>| 1 + children.iter().sum::<usize>())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(subtree_size, 4);
+    /// ```
+    ///
+    pub fn fold_subtree<A, F>(&self, node_id: &NodeId, mut f: F) -> Result<A, NodeIdError>
+    where
+        F: FnMut(&Node<T>, Vec<A>) -> A,
+    {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::fold_subtree: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        let mut ids = Vec::new();
+        PostOrderTraversal::collect_ids(node_id, self, &mut ids);
+
+        // In post-order the results for a node's children are always the topmost ones.
+        let mut results: Vec<A> = Vec::new();
+        for id in ids {
+            let node = self.get_unsafe(&id);
+            let child_results = results.split_off(results.len() - node.children().len());
+            results.push(f(node, child_results));
+        }
+
+        Ok(results
+            .pop()
+            .expect("Tree::fold_subtree: The sub-tree root was not visited."))
+    }
+
+    /// Returns a `LevelOrderTraversal` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over all of the `Node`s in the sub-tree below a given `Node`.  This
+    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut nodes = tree.traverse_level_order(&root_id).unwrap();
+    ///
+    /// assert_eq!(nodes.next().unwrap().data(), &0);
+    /// assert_eq!(nodes.next().unwrap().data(), &1);
+    /// assert!(nodes.next().is_none());
+    /// ```
+    ///
+    pub fn traverse_level_order(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<LevelOrderTraversal<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::traverse_level_order: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(LevelOrderTraversal::new(self, node_id.clone()))
+    }
+
+    /// Returns a `LevelOrderTraversalIds` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over all of the `NodeIds`s in the sub-tree below a given `NodeId`.  This
+    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut nodes = tree.traverse_level_order_ids(&root_id).unwrap();
+    ///
+    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &0);
+    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &1);
+    /// assert!(nodes.next().is_none());
+    /// ```
+    ///
+    pub fn traverse_level_order_ids(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<LevelOrderTraversalIds<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::traverse_level_order: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(LevelOrderTraversalIds::new(self, node_id.clone()))
+    }
+
+    ///
+    /// Returns a `Descendants` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over all of the `Node`s in the sub-tree below a given `NodeId` in
+    /// Pre-Order Traversal order.  Unlike `traverse_pre_order`, this iterator leaves out the
+    /// sub-tree "root" specified by the `NodeId` given.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+    /// tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let data: Vec<i32> = tree.descendants(&root_id).unwrap().map(|node| *node.data()).collect();
+    ///
+    /// assert_eq!(data, vec![1, 2, 3]);
+    /// ```
+    ///
+    pub fn descendants(&self, node_id: &NodeId) -> Result<Descendants<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error
+                .expect("Tree::descendants: Missing an error value but found an invalid NodeId."));
+        }
+
+        Ok(Descendants::new(self, node_id.clone()))
+    }
+
+    ///
+    /// Returns a `DescendantIds` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over all of the `NodeId`s in the sub-tree below a given `NodeId` in
+    /// Pre-Order Traversal order, leaving out the `NodeId` given.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+    /// tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut descendant_ids = tree.descendant_ids(&root_id).unwrap();
+    ///
+    /// assert_eq!(descendant_ids.next(), Some(node_1));
+    /// assert_eq!(descendant_ids.count(), 2);
+    /// ```
+    ///
+    pub fn descendant_ids(&self, node_id: &NodeId) -> Result<DescendantIds<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::descendant_ids: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(DescendantIds::new(self, node_id.clone()))
+    }
+
+    ///
+    /// Returns a `Leaves` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over the `Node`s without children in the sub-tree below a given `NodeId`,
+    /// from left to right.  If the `Node` given doesn't have any children, it is the only leaf.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+    /// tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let data: Vec<i32> = tree.leaves(&root_id).unwrap().map(|node| *node.data()).collect();
+    ///
+    /// assert_eq!(data, vec![2, 3]);
+    /// ```
+    ///
+    pub fn leaves(&self, node_id: &NodeId) -> Result<Leaves<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(
+                error.expect("Tree::leaves: Missing an error value but found an invalid NodeId.")
+            );
+        }
+
+        Ok(Leaves::new(self, node_id.clone()))
+    }
+
+    ///
+    /// Returns a `LeafIds` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows iteration over the `NodeId`s of the `Node`s without children in the sub-tree below
+    /// a given `NodeId`, from left to right.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+    /// tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut leaf_ids = tree.leaf_ids(&node_1).unwrap();
+    ///
+    /// assert_eq!(tree.get(&leaf_ids.next().unwrap()).unwrap().data(), &2);
+    /// assert!(leaf_ids.next().is_none());
+    /// ```
+    ///
+    pub fn leaf_ids(&self, node_id: &NodeId) -> Result<LeafIds<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(
+                error.expect("Tree::leaf_ids: Missing an error value but found an invalid NodeId.")
+            );
+        }
+
+        Ok(LeafIds::new(self, node_id.clone()))
+    }
+
+    ///
+    /// Returns a `PreOrderWalker` (or a `NodeIdError` if one occurred).
+    ///
+    /// Walks over all of the `NodeId`s in the sub-tree below a given `NodeId` in Pre-Order
+    /// Traversal order, just like `traverse_pre_order_ids`.  Calling `skip_subtree` on the walker
+    /// leaves out everything below the `Node` it returned last, so that a sub-tree can be pruned
+    /// without visiting it.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&node_1)).unwrap();
+    /// tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+    ///
+    /// let mut walker = tree.walk_pre_order(&root_id).unwrap();
+    /// let mut data = Vec::new();
+    /// while let Some(node_id) = walker.next() {
+    ///     if node_id == node_1 {
+    ///         walker.skip_subtree();
+    ///     }
+    ///     data.push(*tree.get(&node_id).unwrap().data());
+    /// }
+    ///
+    /// assert_eq!(data, vec![0, 1, 3]);
+    /// ```
+    ///
+    pub fn walk_pre_order(&self, node_id: &NodeId) -> Result<PreOrderWalker<'_, T>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(node_id);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::walk_pre_order: Missing an error value but found an invalid NodeId.",
+            ));
+        }
+
+        Ok(PreOrderWalker::new(self, node_id.clone()))
+    }
+
+    ///
+    /// Returns a `LevelOrderTraversalMut` iterator (or a `NodeIdError` if one occurred).
+    ///
+    /// Allows changing the data of all of the `Node`s in the sub-tree below a given `NodeId`
+    /// (including that `Node` itself) while iterating over them in Level-Order.  Each item is the
+    /// `NodeId` of a `Node` along with a mutable reference to its data.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let node_1 = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// for (_, data) in tree.traverse_level_order_mut(&root_id).unwrap() {
+    ///     *data += 10;
+    /// }
     ///
-    /// assert_eq!(nodes.next().unwrap().data(), &0);
-    /// assert_eq!(nodes.next().unwrap().data(), &1);
-    /// assert!(nodes.next().is_none());
+    /// assert_eq!(tree.get(&root_id).unwrap().data(), &10);
+    /// assert_eq!(tree.get(&node_1).unwrap().data(), &11);
     /// ```
     ///
-    pub fn traverse_pre_order(
-        &self,
+    pub fn traverse_level_order_mut(
+        &mut self,
         node_id: &NodeId,
-    ) -> Result<PreOrderTraversal<T>, NodeIdError> {
+    ) -> Result<LevelOrderTraversalMut<'_, T>, NodeIdError> {
         let (is_valid, error) = self.is_valid_node_id(node_id);
         if !is_valid {
             return Err(error.expect(
-                "Tree::traverse_pre_order: Missing an error value but found an invalid NodeId.",
+                "Tree::traverse_level_order_mut: Missing an error value but found an invalid NodeId.",
             ));
         }
 
-        Ok(PreOrderTraversal::new(self, node_id.clone()))
+        Ok(LevelOrderTraversalMut::new(self, node_id.clone()))
     }
 
-    /// Returns a `PreOrderTraversalIds` iterator (or a `NodeIdError` if one occurred).
     ///
-    /// Allows iteration over all of the `NodeId`s in the sub-tree below a given `NodeId`.  This
-    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    /// Writes the `Tree` in the Graphviz DOT language.  The `DotOptions` given determine how each
+    /// `Node` is labeled and which attributes it gets.
+    ///
+    /// `Node`s are written in pre-order, followed by one edge from every `Node` to each of its
+    /// children (in order).  Writes an empty graph if the `Tree` is empty.
     ///
     /// ```
     /// use id_tree::*;
     /// use id_tree::InsertBehavior::*;
     ///
-    /// let mut tree: Tree<i32> = Tree::new();
-    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
-    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
-    ///
-    /// let mut nodes = tree.traverse_pre_order_ids(&root_id).unwrap();
+    /// let mut tree: Tree<&str> = Tree::new();
+    /// let root_id = tree.insert(Node::new("root"), AsRoot).unwrap();
+    /// tree.insert(Node::new("say \"hi\""), UnderNode(&root_id)).unwrap();
     ///
-    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &0);
-    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &1);
-    /// assert!(nodes.next().is_none());
+    /// let mut s = String::new();
+    /// tree.write_dot(&mut s, &DotOptions::new()).unwrap();
+    /// assert_eq!(&s, "\
+    /// digraph {
+    ///     n0 [label=\"root\"];
+    ///     n1 [label=\"say \\\"hi\\\"\"];
+    ///     n0 -> n1;
+    /// }
+    /// ");
     /// ```
     ///
-    pub fn traverse_pre_order_ids(
+    pub fn write_dot<W: std::fmt::Write>(
         &self,
-        node_id: &NodeId,
-    ) -> Result<PreOrderTraversalIds<T>, NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(&node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::traverse_pre_order_ids: Missing an error value but found an invalid NodeId.",
-            ));
+        w: &mut W,
+        options: &DotOptions<T>,
+    ) -> std::fmt::Result {
+        let subtree_roots: Vec<NodeId> = if options.orphans() {
+            self.forest_roots().collect()
+        } else {
+            self.root.iter().cloned().collect()
+        };
+
+        let ids: Vec<NodeId> = subtree_roots
+            .into_iter()
+            .flat_map(|root_id| PreOrderTraversalIds::new(self, root_id))
+            .collect();
+
+        writeln!(w, "digraph {{")?;
+        for node_id in &ids {
+            options.write_node(w, node_id.index, self.get_unsafe(node_id).data())?;
+        }
+        for node_id in &ids {
+            for child_id in self.get_unsafe(node_id).children() {
+                writeln!(w, "    n{} -> n{};", node_id.index, child_id.index)?;
+            }
         }
+        writeln!(w, "}}")
+    }
 
-        Ok(PreOrderTraversalIds::new(self, node_id.clone()))
+    ///
+    /// Stops recording changes to the `Tree` and forgets everything that was recorded so far.
+    ///
+    pub fn disable_journal(&mut self) {
+        self.journal = None;
     }
 
-    /// Returns a `PostOrderTraversal` iterator (or a `NodeIdError` if one occurred).
     ///
-    /// Allows iteration over all of the `Node`s in the sub-tree below a given `Node`.  This
-    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    /// Returns `true` if there is a change that can be undone with `undo`.
+    ///
+    pub fn can_undo(&self) -> bool {
+        self.journal
+            .as_ref()
+            .is_some_and(|journal| journal.can_undo())
+    }
+
+    ///
+    /// Returns `true` if there is an undone change that can be redone with `redo`.
+    ///
+    pub fn can_redo(&self) -> bool {
+        self.journal
+            .as_ref()
+            .is_some_and(|journal| journal.can_redo())
+    }
+
+    ///
+    /// Reverts the last change that was recorded in the journal (see `enable_journal`).
+    ///
+    /// The `Tree` ends up in exactly the structure it had before that change, including the order
+    /// of all children and the `NodeId`s of all `Node`s.  The data of `Node`s that stay in the
+    /// `Tree` is not touched.
+    ///
+    /// Returns `false` if there was nothing to undo.
     ///
     /// ```
     /// use id_tree::*;
     /// use id_tree::InsertBehavior::*;
+    /// use id_tree::RemoveBehavior::*;
     ///
     /// let mut tree: Tree<i32> = Tree::new();
+    /// tree.enable_journal();
+    ///
     /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
-    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.remove_node(child_id.clone(), DropChildren).unwrap();
     ///
-    /// let mut nodes = tree.traverse_post_order(&root_id).unwrap();
+    /// assert!(tree.undo());
+    /// assert_eq!(tree.get(&child_id).unwrap().data(), &1);
     ///
-    /// assert_eq!(nodes.next().unwrap().data(), &1);
-    /// assert_eq!(nodes.next().unwrap().data(), &0);
-    /// assert!(nodes.next().is_none());
+    /// assert!(tree.undo());
+    /// assert!(tree.get(&child_id).is_err());
+    ///
+    /// assert!(tree.redo());
+    /// assert_eq!(tree.get(&root_id).unwrap().children(), &vec![child_id]);
     /// ```
     ///
-    pub fn traverse_post_order(
-        &self,
-        node_id: &NodeId,
-    ) -> Result<PostOrderTraversal<T>, NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::traverse_post_order: Missing an error value but found an invalid NodeId.",
-            ));
+    pub fn undo(&mut self) -> bool {
+        let mut journal = self.journal.take();
+        let undone = journal.as_mut().is_some_and(|journal| journal.undo(self));
+        self.journal = journal;
+        if undone {
+            self.revision = ProcessUniqueId::new();
+            self.emit(TreeEvent::Reset);
         }
+        self.debug_check_integrity();
+        undone
+    }
 
-        Ok(PostOrderTraversal::new(self, node_id.clone()))
+    ///
+    /// Re-applies the last change that was reverted with `undo`.  Recording a new change forgets
+    /// all changes that could have been redone.
+    ///
+    /// Returns `false` if there was nothing to redo.
+    ///
+    pub fn redo(&mut self) -> bool {
+        let mut journal = self.journal.take();
+        let redone = journal.as_mut().is_some_and(|journal| journal.redo(self));
+        self.journal = journal;
+        if redone {
+            self.revision = ProcessUniqueId::new();
+            self.emit(TreeEvent::Reset);
+        }
+        self.debug_check_integrity();
+        redone
     }
 
-    /// Returns a `PostOrderTraversalIds` iterator (or a `NodeIdError` if one occurred).
     ///
-    /// Allows iteration over all of the `NodeId`s in the sub-tree below a given `NodeId`.  This
-    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    /// Starts collecting a `TreeEvent` for every change made to the `Tree`.  The events can be
+    /// taken out with `drain_events`.
+    ///
+    /// Events are reported by `insert`, `remove_node`, `remove_subtree`, `drop_orphans`, `graft`,
+    /// `move_node`, `swap_nodes`, the `sort_children_by*` and `make_*_sibling` methods,
+    /// `duplicate_subtree`, `copy_subtree` (on the destination `Tree`) and `replace_data`.  Changes
+    /// to the data of a `Node` that are made through a mutable reference (for example from
+    /// `get_mut`) are not reported.  `undo` and `redo` report a single `TreeEvent::Reset`, since
+    /// they can change any part of the `Tree` at once.
     ///
     /// ```
     /// use id_tree::*;
     /// use id_tree::InsertBehavior::*;
     ///
     /// let mut tree: Tree<i32> = Tree::new();
-    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
-    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.enable_events();
     ///
-    /// let mut nodes = tree.traverse_post_order_ids(&root_id).unwrap();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
     ///
-    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &1);
-    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &0);
-    /// assert!(nodes.next().is_none());
+    /// assert_eq!(
+    ///     tree.drain_events(),
+    ///     vec![
+    ///         TreeEvent::Inserted { id: root_id.clone(), parent: None, index: 0 },
+    ///         TreeEvent::Inserted { id: child_id, parent: Some(root_id), index: 0 },
+    ///     ]
+    /// );
+    /// assert!(tree.drain_events().is_empty());
     /// ```
     ///
-    pub fn traverse_post_order_ids(
-        &self,
-        node_id: &NodeId,
-    ) -> Result<PostOrderTraversalIds<'_, T>, NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::traverse_post_order_ids: Missing an error value but found an invalid NodeId.",
-            ));
+    pub fn enable_events(&mut self) {
+        if self.events.is_none() {
+            self.events = Some(EventQueue::default());
         }
+    }
 
-        Ok(PostOrderTraversalIds::new(self, node_id.clone()))
+    ///
+    /// Stops collecting events and drops all events that have not been drained yet.
+    ///
+    pub fn disable_events(&mut self) {
+        self.events = None;
     }
 
-    /// Returns a `LevelOrderTraversal` iterator (or a `NodeIdError` if one occurred).
     ///
-    /// Allows iteration over all of the `Node`s in the sub-tree below a given `Node`.  This
-    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    /// Takes all events that were collected since the last call, oldest first.  Returns an empty
+    /// `Vec` if events are not being collected.
+    ///
+    pub fn drain_events(&mut self) -> Vec<TreeEvent> {
+        match self.events {
+            Some(ref mut events) => events.drain(),
+            None => Vec::new(),
+        }
+    }
+
+    ///
+    /// Replaces the data of a `Node` and returns the old data.  Unlike changing the data through
+    /// `get_mut`, this reports a `TreeEvent::DataReplaced` event.
+    ///
+    /// Returns a `Result` containing the old data or a `NodeIdError` if one occurred.
     ///
     /// ```
     /// use id_tree::*;
@@ -1499,63 +3616,76 @@ impl<T> Tree<T> {
     ///
     /// let mut tree: Tree<i32> = Tree::new();
     /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
-    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
-    ///
-    /// let mut nodes = tree.traverse_level_order(&root_id).unwrap();
     ///
-    /// assert_eq!(nodes.next().unwrap().data(), &0);
-    /// assert_eq!(nodes.next().unwrap().data(), &1);
-    /// assert!(nodes.next().is_none());
+    /// assert_eq!(tree.replace_data(&root_id, 1), Ok(0));
+    /// assert_eq!(tree.get(&root_id).unwrap().data(), &1);
     /// ```
     ///
-    pub fn traverse_level_order(
-        &self,
-        node_id: &NodeId,
-    ) -> Result<LevelOrderTraversal<T>, NodeIdError> {
+    pub fn replace_data(&mut self, node_id: &NodeId, data: T) -> Result<T, NodeIdError> {
         let (is_valid, error) = self.is_valid_node_id(node_id);
         if !is_valid {
             return Err(error.expect(
-                "Tree::traverse_level_order: Missing an error value but found an invalid NodeId.",
+                "Tree::replace_data: Missing an error value but found an invalid NodeId.",
             ));
         }
 
-        Ok(LevelOrderTraversal::new(self, node_id.clone()))
+        let old_data = self.get_mut_unsafe(node_id).replace_data(data);
+        self.emit(TreeEvent::DataReplaced {
+            id: node_id.clone(),
+        });
+
+        Ok(old_data)
     }
 
-    /// Returns a `LevelOrderTraversalIds` iterator (or a `NodeIdError` if one occurred).
     ///
-    /// Allows iteration over all of the `NodeIds`s in the sub-tree below a given `NodeId`.  This
-    /// iterator will always include that sub-tree "root" specified by the `NodeId` given.
+    /// Checks the internal structure of the `Tree`: every child points back to its parent and is
+    /// listed by it exactly once, there are no cycles, the root `Node` has no parent, the free
+    /// `NodeId`s match the empty slots exactly and every `NodeId` belongs to this `Tree`.
+    ///
+    /// None of this should ever be broken, so this is mostly useful in tests.  With the
+    /// `integrity_checks` feature enabled, debug builds run this check after every change to the
+    /// structure of a `Tree` and panic if it fails.  Since the check looks at every `Node`, this
+    /// makes every change take O(n) time, so building a `Tree` becomes quadratic.
+    ///
+    /// Returns an `IntegrityError` describing the first problem found.
     ///
     /// ```
     /// use id_tree::*;
     /// use id_tree::InsertBehavior::*;
+    /// use id_tree::SwapBehavior::*;
     ///
     /// let mut tree: Tree<i32> = Tree::new();
     /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
-    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
-    ///
-    /// let mut nodes = tree.traverse_level_order_ids(&root_id).unwrap();
+    /// let first_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// let second_id = tree.insert(Node::new(2), UnderNode(&first_id)).unwrap();
+    /// tree.swap_nodes(&first_id, &second_id, TakeChildren).unwrap();
     ///
-    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &0);
-    /// assert_eq!(tree.get(&nodes.next().unwrap()).unwrap().data(), &1);
-    /// assert!(nodes.next().is_none());
+    /// assert_eq!(tree.check_integrity(), Ok(()));
     /// ```
     ///
-    pub fn traverse_level_order_ids(
-        &self,
-        node_id: &NodeId,
-    ) -> Result<LevelOrderTraversalIds<T>, NodeIdError> {
-        let (is_valid, error) = self.is_valid_node_id(node_id);
-        if !is_valid {
-            return Err(error.expect(
-                "Tree::traverse_level_order: Missing an error value but found an invalid NodeId.",
-            ));
-        }
+    pub fn check_integrity(&self) -> Result<(), IntegrityError> {
+        integrity::check(
+            self.id,
+            self.root.as_ref(),
+            &self.nodes,
+            &self.generations,
+            &self.free_ids,
+        )
+    }
 
-        Ok(LevelOrderTraversalIds::new(self, node_id.clone()))
+    #[cfg(all(feature = "integrity_checks", debug_assertions))]
+    fn debug_check_integrity(&self) {
+        if let Err(error) = self.check_integrity() {
+            panic!(
+                "Tree: The Tree is broken after a change ({}). Please report this issue!",
+                error
+            );
+        }
     }
 
+    #[cfg(not(all(feature = "integrity_checks", debug_assertions)))]
+    fn debug_check_integrity(&self) {}
+
     // Nothing should make it past this function.
     // If there is a way for a NodeId to be invalid, it should be caught here.
     fn is_valid_node_id(&self, node_id: &NodeId) -> (bool, Option<NodeIdError>) {
@@ -1564,17 +3694,19 @@ impl<T> Tree<T> {
         }
 
         if node_id.index >= self.nodes.len() {
-            panic!(
-                "NodeId: {:?} is out of bounds. This is most likely a bug in id_tree. Please \
-                 report this issue!",
-                node_id
-            );
+            // undoing an insertion can shrink the Tree again
+            return (false, Some(NodeIdError::NodeIdNoLongerValid));
         }
 
         unsafe {
             if self.nodes.get_unchecked(node_id.index).is_none() {
                 return (false, Some(NodeIdError::NodeIdNoLongerValid));
             }
+
+            if *self.generations.get_unchecked(node_id.index) != node_id.generation {
+                // the slot has been re-used since this NodeId was handed out
+                return (false, Some(NodeIdError::NodeIdNoLongerValid));
+            }
         }
 
         (true, None)
@@ -1617,18 +3749,53 @@ impl<T> Tree<T> {
                 .pop()
                 .expect("Tree::insert_new_node: Couldn't pop from Vec with len() > 0.");
 
+            if let Some(ref mut journal) = self.journal {
+                journal.record_free_pop(self.free_ids.len(), &new_node_id);
+                journal.record_slot(&self.nodes, &self.generations, new_node_id.index);
+            }
+
             self.nodes.push(Some(new_node));
             self.nodes.swap_remove(new_node_id.index);
 
+            // the generation of this slot was already bumped when it was freed, but an undone
+            // insertion may have used that generation since
+            let min_generation = self.min_generation(new_node_id.index);
+            if self.generations[new_node_id.index] < min_generation {
+                self.generations[new_node_id.index] = min_generation;
+                return self.new_node_id(new_node_id.index);
+            }
+
             new_node_id
         } else {
             let new_node_index = self.nodes.len();
+            if let Some(ref mut journal) = self.journal {
+                journal.record_slot(&self.nodes, &self.generations, new_node_index);
+            }
+
             self.nodes.push(Some(new_node));
+            let generation = self.min_generation(new_node_index);
+            self.generations.push(generation);
 
             self.new_node_id(new_node_index)
         }
     }
 
+    fn min_generation(&self, node_index: usize) -> usize {
+        self.min_generations.get(node_index).cloned().unwrap_or(0)
+    }
+
+    /// Makes sure that no `Node` inserted into the given slot from now on gets a generation below
+    /// `generation`.
+    ///
+    pub(crate) fn raise_min_generation(&mut self, node_index: usize, generation: usize) {
+        if self.min_generations.len() <= node_index {
+            self.min_generations.resize(node_index + 1, 0);
+        }
+        if self.min_generations[node_index] < generation {
+            self.min_generations[node_index] = generation;
+        }
+    }
+
     fn remove_node_internal(&mut self, node_id: NodeId) -> Node<T> {
         if let Some(root_id) = self.root.clone() {
             if node_id == root_id {
@@ -1655,20 +3822,34 @@ impl<T> Tree<T> {
     }
 
     fn take_node(&mut self, node_id: NodeId) -> Node<T> {
+        if let Some(ref mut journal) = self.journal {
+            journal.record_slot(&self.nodes, &self.generations, node_id.index);
+        }
+
         self.nodes.push(None);
         let node = self.nodes.swap_remove(node_id.index).expect(
             "Tree::take_node: An invalid NodeId made it past id_tree's internal checks. \
              Please report this issue!",
         );
-        self.free_ids.push(node_id);
+
+        if let Some(ref mut journal) = self.journal {
+            journal.record_taken(node_id.index, &node.data);
+        }
+
+        let generation = &mut self.generations[node_id.index];
+        *generation = generation.wrapping_add(1);
+
+        let free_id = self.new_node_id(node_id.index);
+        self.free_ids.push(free_id);
 
         node
     }
 
-    fn new_node_id(&self, node_index: usize) -> NodeId {
+    pub(crate) fn new_node_id(&self, node_index: usize) -> NodeId {
         NodeId {
             tree_id: self.id,
             index: node_index,
+            generation: self.generations[node_index],
         }
     }
 
@@ -1690,6 +3871,27 @@ impl<T> Tree<T> {
         }
     }
 
+    // Returns the children of the parent of a (valid) `Node` along with the position of the
+    // `Node` amongst them, or `None` if the `Node` doesn't have a parent.
+    fn siblings_around(&self, node_id: &NodeId) -> Option<(&[NodeId], usize)> {
+        let parent_id = self.get_unsafe(node_id).parent()?;
+        let siblings = self.get_unsafe(parent_id).children().as_slice();
+        let index = siblings
+            .iter()
+            .position(|sibling_id| sibling_id == node_id)
+            .expect("Tree::siblings_around: A Node is missing from the children of its parent.");
+
+        Some((siblings, index))
+    }
+
+    // Returns the siblings before and after a (valid) `Node`.
+    fn split_siblings(&self, node_id: &NodeId) -> (&[NodeId], &[NodeId]) {
+        match self.siblings_around(node_id) {
+            Some((siblings, index)) => (&siblings[..index], &siblings[index + 1..]),
+            None => (&[], &[]),
+        }
+    }
+
     pub(crate) fn get_unsafe(&self, node_id: &NodeId) -> &Node<T> {
         unsafe {
             self.nodes.get_unchecked(node_id.index).as_ref().expect(
@@ -1700,6 +3902,10 @@ impl<T> Tree<T> {
     }
 
     fn get_mut_unsafe(&mut self, node_id: &NodeId) -> &mut Node<T> {
+        if let Some(ref mut journal) = self.journal {
+            journal.record_slot(&self.nodes, &self.generations, node_id.index);
+        }
+
         unsafe {
             self.nodes.get_unchecked_mut(node_id.index).as_mut().expect(
                 "Tree::get_mut_unsafe: An invalid NodeId made it past id_tree's internal \
@@ -1707,6 +3913,41 @@ impl<T> Tree<T> {
             )
         }
     }
+
+    /// Runs a mutation of the `Tree`.  Every public method that changes the structure of the
+    /// `Tree` goes through here so that the change can be recorded in the journal and so that
+    /// only the outermost of nested mutations reports events.
+    ///
+    fn mutate<R, F>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Tree<T>) -> R,
+    {
+        if let Some(ref mut journal) = self.journal {
+            journal.begin(self.root.clone(), self.nodes.len(), self.free_ids.len());
+        }
+        if let Some(ref mut events) = self.events {
+            events.begin();
+        }
+
+        let result = f(self);
+
+        if let Some(ref mut events) = self.events {
+            events.end();
+        }
+        if let Some(ref mut journal) = self.journal {
+            journal.end();
+        }
+        self.revision = ProcessUniqueId::new();
+        self.debug_check_integrity();
+
+        result
+    }
+
+    fn emit(&mut self, event: TreeEvent) {
+        if let Some(ref mut events) = self.events {
+            events.push(event);
+        }
+    }
 }
 impl<T> Default for Tree<T> {
     fn default() -> Self {
@@ -1714,6 +3955,35 @@ impl<T> Default for Tree<T> {
     }
 }
 
+///
+/// Parses the text layout written by a `TreeFormatter` created with `TreeFormatter::new`, which
+/// labels every `Node` with the `Display` representation of its data, see `Tree::parse_formatted`.
+///
+/// The output of `write_formatted` uses the `Debug` representation instead, so it can only be
+/// parsed back this way if `Debug` and `FromStr` agree for `T` (as they do for numbers, but not
+/// for `String`s, whose `Debug` representation is quoted).
+///
+/// ```
+/// use id_tree::*;
+/// use id_tree::InsertBehavior::*;
+///
+/// let mut tree: Tree<String> = Tree::new();
+/// let root_id = tree.insert(Node::new("root".to_string()), AsRoot).unwrap();
+/// tree.insert(Node::new("child".to_string()), UnderNode(&root_id)).unwrap();
+///
+/// let mut s = String::new();
+/// TreeFormatter::new().write(&tree, &mut s).unwrap();
+/// assert_eq!(s.parse::<Tree<String>>().unwrap(), tree);
+/// ```
+///
+impl<T: FromStr> FromStr for Tree<T> {
+    type Err = ParseError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tree::parse_formatted(s, str::parse)
+    }
+}
+
 impl<T> PartialEq for Tree<T>
 where
     T: PartialEq,
@@ -1762,6 +4032,7 @@ where
             root: self.root.as_ref().map(|x| NodeId {
                 tree_id,
                 index: x.index,
+                generation: x.generation,
             }),
             nodes: self
                 .nodes
@@ -1772,6 +4043,7 @@ where
                         parent: y.parent.as_ref().map(|z| NodeId {
                             tree_id,
                             index: z.index,
+                            generation: z.generation,
                         }),
                         children: y
                             .children
@@ -1779,26 +4051,233 @@ where
                             .map(|z| NodeId {
                                 tree_id,
                                 index: z.index,
+                                generation: z.generation,
                             })
                             .collect(),
                     })
                 })
                 .collect(),
+            generations: self.generations.clone(),
             free_ids: self
                 .free_ids
                 .iter()
                 .map(|x| NodeId {
                     tree_id,
                     index: x.index,
+                    generation: x.generation,
                 })
                 .collect(),
+            min_generations: Vec::new(),
+            revision: ProcessUniqueId::new(),
+            journal: None,
+            events: None,
+        }
+    }
+}
+
+// The fields of a `Tree` as they are written by `Serialize`, before they have been checked.
+#[cfg(feature = "serde_support")]
+#[derive(Deserialize)]
+#[serde(rename = "Tree")]
+struct RawTree<T> {
+    #[serde(rename = "id")]
+    _id: ProcessUniqueId,
+    root: Option<NodeId>,
+    nodes: Vec<Option<Node<T>>>,
+    // missing in `Tree`s written before slots had generations
+    #[serde(default)]
+    generations: Option<Vec<usize>>,
+    free_ids: Vec<NodeId>,
+}
+
+///
+/// Deserializing a `Tree` checks its internal structure, so that broken input results in an
+/// error instead of a `Tree` that panics later on.
+///
+/// `Tree`s written by versions that didn't have generations yet (1.8.0 and earlier) can still be
+/// read.  All of their slots start out with a generation of 0.
+///
+/// The deserialized `Tree` always gets a fresh id, so it never accepts the `NodeId`s of another
+/// `Tree`.  Use `NodeIdSeed` to read `NodeId`s that were written along with it.
+///
+#[cfg(feature = "serde_support")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Tree<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Tree<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = RawTree::deserialize(deserializer)?;
+        let slots = raw.nodes.len();
+        let generations = raw.generations.unwrap_or_else(|| vec![0; slots]);
+        integrity::check(
+            detached_tree_id(),
+            raw.root.as_ref(),
+            &raw.nodes,
+            &generations,
+            &raw.free_ids,
+        )
+        .map_err(D::Error::custom)?;
+
+        Ok(Tree::with_layout_of(
+            raw.nodes,
+            raw.root,
+            generations,
+            raw.free_ids,
+        ))
+    }
+}
+
+impl<T: Clone> Tree<T> {
+    ///
+    /// Starts recording all changes to the structure of the `Tree` so that they can be reverted
+    /// with `undo` and re-applied with `redo`.
+    ///
+    /// Every call to one of the methods that change the structure of the `Tree` (`insert`,
+    /// `remove_node`, `move_node`, `swap_nodes`, the `sort_children_by*` and `make_*_sibling`
+    /// methods, and so on) is recorded as a single change.  Changes to the data of a `Node` (for
+    /// example through `get_mut`) are not recorded.
+    ///
+    /// The data of removed `Node`s is cloned so that they can be restored later.
+    ///
+    /// `undo` and `redo` restore the `NodeId`s of the `Node`s they bring back exactly.  The
+    /// `NodeId` returned by an insertion that was undone is never handed out again for a
+    /// different `Node`, though; it only becomes valid again if the insertion is redone.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    ///
+    /// tree.enable_journal();
+    /// tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    ///
+    /// assert!(tree.can_undo());
+    /// tree.undo();
+    ///
+    /// assert!(!tree.can_undo());
+    /// assert_eq!(tree.get(&root_id).unwrap().children().len(), 0);
+    /// ```
+    ///
+    pub fn enable_journal(&mut self) {
+        if self.journal.is_none() {
+            self.journal = Some(Box::new(Journal::new(T::clone)));
+        }
+    }
+
+    /// Copies the subtree rooted at the `Node` with the given `NodeId` into another `Tree`.  The
+    /// copied root `Node` is inserted according to the `InsertBehavior` provided and all of its
+    /// descendants keep their place (and order) below it.  The data of every `Node` is cloned.
+    ///
+    /// Returns a `Result` containing the `NodeId` of the copied root `Node` in the destination
+    /// `Tree`, or a `NodeIdError` if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+    ///
+    /// let mut other: Tree<i32> = Tree::new();
+    /// let copy_id = tree.copy_subtree(&child_id, &mut other, AsRoot).unwrap();
+    ///
+    /// assert_eq!(other.get(&copy_id).unwrap().data(), &1);
+    /// assert_eq!(other.children(&copy_id).unwrap().next().unwrap().data(), &2);
+    /// # assert_eq!(tree.traverse_pre_order(&root_id).unwrap().count(), 3);
+    /// ```
+    ///
+    pub fn copy_subtree(
+        &self,
+        src: &NodeId,
+        dest: &mut Tree<T>,
+        behavior: InsertBehavior,
+    ) -> Result<NodeId, NodeIdError> {
+        let nodes = self.clone_subtree_nodes(src)?;
+        dest.mutate(|dest| dest.insert_cloned_subtree(src, nodes, behavior))
+    }
+
+    /// Copies the subtree rooted at the `Node` with the given `NodeId` to another place in this
+    /// `Tree`.  The copied root `Node` is inserted according to the `InsertBehavior` provided and
+    /// all of its descendants keep their place (and order) below it.  The data of every `Node`
+    /// is cloned.
+    ///
+    /// Returns a `Result` containing the `NodeId` of the copied root `Node`, or a `NodeIdError`
+    /// if one occurred.
+    ///
+    /// ```
+    /// use id_tree::*;
+    /// use id_tree::InsertBehavior::*;
+    ///
+    /// let mut tree: Tree<i32> = Tree::new();
+    /// let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+    /// let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+    /// tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+    ///
+    /// let copy_id = tree.duplicate_subtree(&child_id, AfterSibling(&child_id)).unwrap();
+    ///
+    /// assert_eq!(tree.get(&root_id).unwrap().children(), &vec![child_id, copy_id.clone()]);
+    /// assert_eq!(tree.children(&copy_id).unwrap().next().unwrap().data(), &2);
+    /// ```
+    ///
+    pub fn duplicate_subtree(
+        &mut self,
+        src: &NodeId,
+        behavior: InsertBehavior,
+    ) -> Result<NodeId, NodeIdError> {
+        self.mutate(|tree| {
+            let nodes = tree.clone_subtree_nodes(src)?;
+            tree.insert_cloned_subtree(src, nodes, behavior)
+        })
+    }
+
+    /// Clones all `Node`s of the subtree rooted at `src` in pre-order.  The cloned `Node`s keep
+    /// their parent links (which `insert_pre_ordered` relies on) but no children.
+    ///
+    fn clone_subtree_nodes(&self, src: &NodeId) -> Result<Vec<(NodeId, Node<T>)>, NodeIdError> {
+        let (is_valid, error) = self.is_valid_node_id(src);
+        if !is_valid {
+            return Err(error.expect(
+                "Tree::clone_subtree_nodes: Missing an error value but found an invalid NodeId.",
+            ));
         }
+
+        Ok(PreOrderTraversalIds::new(self, src.clone())
+            .map(|id| {
+                let node = self.get_unsafe(&id);
+                let copy = Node {
+                    data: node.data.clone(),
+                    parent: node.parent.clone(),
+                    children: Vec::new(),
+                };
+                (id, copy)
+            })
+            .collect())
+    }
+
+    fn insert_cloned_subtree(
+        &mut self,
+        src: &NodeId,
+        nodes: Vec<(NodeId, Node<T>)>,
+        behavior: InsertBehavior,
+    ) -> Result<NodeId, NodeIdError> {
+        let mut id_map = self.insert_pre_ordered(nodes, behavior)?;
+        Ok(id_map
+            .remove(src)
+            .expect("Tree::insert_cloned_subtree: The copied root is missing from the id map."))
     }
 }
 
 impl<T: std::fmt::Debug> Tree<T> {
     /// Write formatted tree representation and nodes with debug formatting.
     ///
+    /// Use a `TreeFormatter` for more control over the output.  The output can be parsed back
+    /// with `Tree::parse_formatted`; parsing it with `str::parse` only gives back the same `Tree`
+    /// if the `Debug` and `FromStr` representations of `T` agree.
+    ///
     /// Example:
     ///
     /// ```
@@ -1832,51 +4311,10 @@ impl<T: std::fmt::Debug> Tree<T> {
     /// assert_eq!(&s, "");
     /// ```
     pub fn write_formatted<W: std::fmt::Write>(&self, w: &mut W) -> std::fmt::Result {
-        if let Some(node_id) = self.root_node_id() {
-            let childn = 0;
-            let level = 0;
-            let last = vec![];
-            let mut stack = vec![(node_id, childn, level, last)];
-            while let Some((node_id, childn, level, last)) = stack.pop() {
-                debug_assert_eq!(
-                    last.len(),
-                    level,
-                    "each previous level should indicate whether it has reached the last node"
-                );
-                let node = self
-                    .get(node_id)
-                    .expect("getting node of existing node ref id");
-                if childn == 0 {
-                    for i in 1..level {
-                        if last[i - 1] {
-                            write!(w, "    ")?;
-                        } else {
-                            write!(w, "│   ")?;
-                        }
-                    }
-                    if level > 0 {
-                        if last[level - 1] {
-                            write!(w, "└── ")?;
-                        } else {
-                            write!(w, "├── ")?;
-                        }
-                    }
-                    writeln!(w, "{:?}", node.data())?;
-                }
-                let mut children = node.children().iter().skip(childn);
-                if let Some(child_id) = children.next() {
-                    let mut next_last = last.clone();
-                    if children.next().is_some() {
-                        stack.push((node_id, childn + 1, level, last));
-                        next_last.push(false);
-                    } else {
-                        next_last.push(true);
-                    }
-                    stack.push((child_id, 0, level + 1, next_last));
-                }
-            }
-        }
-        Ok(())
+        // starting from the root can't fail, so only errors of the writer are left
+        TreeFormatter::from_label_fn(|data: &T| format!("{:?}", data))
+            .write(self, w)
+            .map_err(|_| std::fmt::Error)
     }
 }
 
@@ -1952,6 +4390,7 @@ mod tree_builder_tests {
 mod tree_tests {
     use super::super::Node;
     use super::super::NodeId;
+    use super::super::NodeIdError;
     use super::Tree;
     use super::TreeBuilder;
 
@@ -1995,6 +4434,7 @@ mod tree_tests {
     }
 
     #[test]
+    #[allow(clippy::get_first, clippy::needless_borrow)]
     fn test_set_root() {
         use InsertBehavior::*;
 
@@ -2043,6 +4483,7 @@ mod tree_tests {
     }
 
     #[test]
+    #[allow(clippy::get_first, clippy::needless_borrow)]
     fn test_insert_with_parent() {
         use InsertBehavior::*;
 
@@ -2080,6 +4521,64 @@ mod tree_tests {
         assert_eq!(child_2_ref.data(), &b);
     }
 
+    #[test]
+    fn test_insert_under_node_at() {
+        use InsertBehavior::*;
+
+        let mut tree = TreeBuilder::new().with_root(Node::new(0)).build();
+        let root_id = tree.root.clone().unwrap();
+
+        let node_2_id = tree.insert(Node::new(2), UnderNodeAt(&root_id, 0)).unwrap();
+        let node_1_id = tree.insert(Node::new(1), UnderNodeAt(&root_id, 0)).unwrap();
+        let node_4_id = tree.insert(Node::new(4), UnderNodeAt(&root_id, 2)).unwrap();
+        let node_3_id = tree.insert(Node::new(3), UnderNodeAt(&root_id, 2)).unwrap();
+
+        assert_eq!(
+            tree.get(&root_id).unwrap().children(),
+            &vec![node_1_id.clone(), node_2_id, node_3_id, node_4_id]
+        );
+        assert_eq!(tree.get(&node_1_id).unwrap().parent(), Some(&root_id));
+
+        let result = tree.insert(Node::new(6), UnderNodeAt(&root_id, 5));
+        assert_eq!(result, Err(NodeIdError::InvalidChildIndex));
+        assert_eq!(tree.get(&root_id).unwrap().children().len(), 4);
+    }
+
+    #[test]
+    fn test_insert_next_to_sibling() {
+        use InsertBehavior::*;
+
+        let mut tree = TreeBuilder::new().with_root(Node::new(0)).build();
+        let root_id = tree.root.clone().unwrap();
+
+        let node_2_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let node_1_id = tree
+            .insert(Node::new(1), BeforeSibling(&node_2_id))
+            .unwrap();
+        let node_4_id = tree.insert(Node::new(4), AfterSibling(&node_2_id)).unwrap();
+        let node_5_id = tree.insert(Node::new(5), AfterSibling(&node_4_id)).unwrap();
+        let node_3_id = tree
+            .insert(Node::new(3), BeforeSibling(&node_4_id))
+            .unwrap();
+
+        assert_eq!(
+            tree.get(&root_id).unwrap().children(),
+            &vec![
+                node_1_id,
+                node_2_id,
+                node_3_id.clone(),
+                node_4_id,
+                node_5_id
+            ]
+        );
+        assert_eq!(tree.get(&node_3_id).unwrap().parent(), Some(&root_id));
+
+        let result = tree.insert(Node::new(6), BeforeSibling(&root_id));
+        assert_eq!(result, Err(NodeIdError::NodeHasNoParent));
+        let result = tree.insert(Node::new(6), AfterSibling(&root_id));
+        assert_eq!(result, Err(NodeIdError::NodeHasNoParent));
+    }
+
     #[test]
     fn test_remove_node_lift_children() {
         use InsertBehavior::*;
@@ -2112,57 +4611,457 @@ mod tree_tests {
         assert_eq!(node_2_ref.parent().unwrap(), &root_id);
         assert_eq!(node_3_ref.parent().unwrap(), &root_id);
 
-        assert!(root_ref.children().contains(&node_2_id));
-        assert!(root_ref.children().contains(&node_3_id));
+        assert!(root_ref.children().contains(&node_2_id));
+        assert!(root_ref.children().contains(&node_3_id));
+    }
+
+    #[test]
+    fn test_remove_node_orphan_children() {
+        use InsertBehavior::*;
+        use RemoveBehavior::*;
+
+        let mut tree = TreeBuilder::new().with_root(Node::new(5)).build();
+
+        let root_id = tree.root.clone().unwrap();
+
+        let node_1_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2_id = tree.insert(Node::new(2), UnderNode(&node_1_id)).unwrap();
+        let node_3_id = tree.insert(Node::new(3), UnderNode(&node_1_id)).unwrap();
+
+        let node_1 = tree.remove_node(node_1_id.clone(), OrphanChildren).unwrap();
+
+        assert_eq!(Some(&root_id), tree.root_node_id());
+
+        assert_eq!(node_1.data(), &1);
+        assert_eq!(node_1.children().len(), 0);
+        assert!(node_1.parent().is_none());
+        assert!(tree.get(&node_1_id).is_err());
+
+        let node_2_ref = tree.get(&node_2_id).unwrap();
+        let node_3_ref = tree.get(&node_3_id).unwrap();
+
+        assert_eq!(node_2_ref.data(), &2);
+        assert_eq!(node_3_ref.data(), &3);
+
+        assert!(node_2_ref.parent().is_none());
+        assert!(node_3_ref.parent().is_none());
+    }
+
+    #[test]
+    fn test_remove_root() {
+        use RemoveBehavior::*;
+
+        let mut tree = TreeBuilder::new().with_root(Node::new(5)).build();
+
+        let root_id = tree.root.clone().unwrap();
+        tree.remove_node(root_id.clone(), OrphanChildren).unwrap();
+        assert_eq!(None, tree.root_node_id());
+
+        let mut tree = TreeBuilder::new().with_root(Node::new(5)).build();
+
+        let root_id = tree.root.clone().unwrap();
+        tree.remove_node(root_id.clone(), LiftChildren).unwrap();
+        assert_eq!(None, tree.root_node_id());
+    }
+
+    #[test]
+    fn test_reused_slot_rejects_old_node_id() {
+        use InsertBehavior::*;
+        use RemoveBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let old_node_1_id = node_1_id.clone();
+
+        tree.remove_node(node_1_id, DropChildren).unwrap();
+        assert_eq!(tree.free_ids.len(), 1);
+
+        let node_2_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+
+        // the slot was re-used, but the NodeIds must differ
+        assert_eq!(node_2_id.index, old_node_1_id.index);
+        assert_ne!(node_2_id.generation, old_node_1_id.generation);
+        assert_ne!(node_2_id, old_node_1_id);
+        assert_eq!(tree.free_ids.len(), 0);
+
+        assert_eq!(
+            tree.get(&old_node_1_id).err(),
+            Some(NodeIdError::NodeIdNoLongerValid)
+        );
+        assert_eq!(tree.get(&node_2_id).unwrap().data(), &2);
+    }
+
+    #[test]
+    fn test_remove_subtree() {
+        use InsertBehavior::*;
+
+        //        0
+        //       / \
+        //      1   2
+        //     /|\
+        //    3 4 5
+        //      |
+        //      6
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let node_3_id = tree.insert(Node::new(3), UnderNode(&node_1_id)).unwrap();
+        let node_4_id = tree.insert(Node::new(4), UnderNode(&node_1_id)).unwrap();
+        let node_5_id = tree.insert(Node::new(5), UnderNode(&node_1_id)).unwrap();
+        let node_6_id = tree.insert(Node::new(6), UnderNode(&node_4_id)).unwrap();
+
+        let (subtree, id_map) = tree.remove_subtree(node_1_id.clone()).unwrap();
+
+        // what's left behind
+        assert_eq!(tree.get(&root_id).unwrap().children(), &vec![node_2_id]);
+        for old_id in &[&node_1_id, &node_3_id, &node_4_id, &node_5_id, &node_6_id] {
+            assert!(tree.get(old_id).is_err());
+        }
+        assert_eq!(tree.free_ids.len(), 5);
+
+        // what was taken out
+        assert_ne!(subtree.id, tree.id);
+        assert_eq!(id_map.len(), 5);
+        assert_eq!(subtree.nodes.len(), 5);
+        assert_eq!(subtree.root_node_id(), Some(&id_map[&node_1_id]));
+
+        let new_root = subtree.get(&id_map[&node_1_id]).unwrap();
+        assert_eq!(new_root.data(), &1);
+        assert!(new_root.parent().is_none());
+        assert_eq!(
+            new_root.children(),
+            &vec![
+                id_map[&node_3_id].clone(),
+                id_map[&node_4_id].clone(),
+                id_map[&node_5_id].clone(),
+            ]
+        );
+
+        let new_node_6 = subtree.get(&id_map[&node_6_id]).unwrap();
+        assert_eq!(new_node_6.data(), &6);
+        assert_eq!(new_node_6.parent(), Some(&id_map[&node_4_id]));
+
+        let data: Vec<i32> = subtree
+            .traverse_pre_order(subtree.root_node_id().unwrap())
+            .unwrap()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(data, vec![1, 3, 4, 6, 5]);
+    }
+
+    #[test]
+    fn test_remove_subtree_of_root() {
+        use InsertBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+
+        let (subtree, id_map) = tree.remove_subtree(root_id.clone()).unwrap();
+
+        assert_eq!(tree.root_node_id(), None);
+        assert!(tree.get(&node_1_id).is_err());
+
+        assert_eq!(subtree.root_node_id(), Some(&id_map[&root_id]));
+        assert_eq!(
+            subtree.get(&id_map[&node_1_id]).unwrap().parent(),
+            Some(&id_map[&root_id])
+        );
+    }
+
+    #[test]
+    fn test_graft() {
+        use InsertBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+
+        //    10
+        //   /  \
+        //  11  12
+        //  |
+        //  13
+        let mut other = Tree::new();
+        let node_10_id = other.insert(Node::new(10), AsRoot).unwrap();
+        let node_11_id = other.insert(Node::new(11), UnderNode(&node_10_id)).unwrap();
+        let node_12_id = other.insert(Node::new(12), UnderNode(&node_10_id)).unwrap();
+        let node_13_id = other.insert(Node::new(13), UnderNode(&node_11_id)).unwrap();
+
+        let id_map = tree.graft(other, AfterSibling(&node_1_id)).unwrap();
+        assert_eq!(id_map.len(), 4);
+
+        assert_eq!(
+            tree.get(&root_id).unwrap().children(),
+            &vec![node_1_id, id_map[&node_10_id].clone(), node_2_id]
+        );
+        assert_eq!(
+            tree.get(&id_map[&node_10_id]).unwrap().children(),
+            &vec![id_map[&node_11_id].clone(), id_map[&node_12_id].clone()]
+        );
+        assert_eq!(
+            tree.get(&id_map[&node_13_id]).unwrap().parent(),
+            Some(&id_map[&node_11_id])
+        );
+
+        let data: Vec<i32> = tree
+            .traverse_pre_order(&root_id)
+            .unwrap()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(data, vec![0, 1, 10, 11, 13, 12, 2]);
+    }
+
+    #[test]
+    fn test_graft_as_root() {
+        use InsertBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+
+        let mut other = Tree::new();
+        let node_10_id = other.insert(Node::new(10), AsRoot).unwrap();
+        let node_11_id = other.insert(Node::new(11), UnderNode(&node_10_id)).unwrap();
+
+        let id_map = tree.graft(other, AsRoot).unwrap();
+
+        assert_eq!(tree.root_node_id(), Some(&id_map[&node_10_id]));
+        let id_map_root = id_map[&node_10_id].clone();
+        assert_eq!(
+            tree.get(&id_map[&node_10_id]).unwrap().children(),
+            &vec![root_id, id_map[&node_11_id].clone()]
+        );
+
+        // grafting an empty tree is a no-op
+        let id_map = tree.graft(Tree::new(), AsRoot).unwrap();
+        assert!(id_map.is_empty());
+        assert_eq!(tree.root_node_id(), Some(&id_map_root));
+    }
+
+    #[test]
+    fn test_copy_subtree() {
+        use InsertBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(2), UnderNode(&node_1_id)).unwrap();
+        tree.insert(Node::new(3), UnderNode(&node_1_id)).unwrap();
+
+        let mut other = Tree::new();
+        let other_root_id = other.insert(Node::new(10), AsRoot).unwrap();
+
+        let copy_id = tree
+            .copy_subtree(&node_1_id, &mut other, UnderNode(&other_root_id))
+            .unwrap();
+
+        assert_eq!(other.get(&copy_id).unwrap().parent(), Some(&other_root_id));
+        let data: Vec<i32> = other
+            .traverse_pre_order(&other_root_id)
+            .unwrap()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(data, vec![10, 1, 2, 3]);
+
+        // the source tree is untouched
+        let data: Vec<i32> = tree
+            .traverse_pre_order(&root_id)
+            .unwrap()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(data, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_duplicate_subtree() {
+        use InsertBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let node_1_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let node_2_id = tree.insert(Node::new(2), UnderNode(&node_1_id)).unwrap();
+
+        // copy a subtree into itself
+        let copy_id = tree
+            .duplicate_subtree(&node_1_id, UnderNode(&node_2_id))
+            .unwrap();
+        assert_eq!(tree.get(&copy_id).unwrap().parent(), Some(&node_2_id));
+
+        let data: Vec<i32> = tree
+            .traverse_pre_order(&root_id)
+            .unwrap()
+            .map(|node| *node.data())
+            .collect();
+        assert_eq!(data, vec![0, 1, 2, 1, 2]);
+
+        // copy the whole tree as a new root
+        let copy_id = tree.duplicate_subtree(&root_id, AsRoot).unwrap();
+        assert_eq!(tree.root_node_id(), Some(&copy_id));
+        assert_eq!(tree.traverse_pre_order(&copy_id).unwrap().count(), 10);
+    }
+
+    #[test]
+    fn test_map() {
+        use InsertBehavior::*;
+        use RemoveBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+        let node_2_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let node_3_id = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+        let node_4_id = tree.insert(Node::new(4), UnderNode(&node_2_id)).unwrap();
+        // leave a free slot behind
+        tree.remove_node(node_3_id.clone(), DropChildren).unwrap();
+
+        let mut seen = Vec::new();
+        let mapped = tree.map(|id, data| {
+            seen.push(id.clone());
+            data * 10
+        });
+        assert_eq!(
+            seen,
+            vec![root_id.clone(), node_2_id.clone(), node_4_id.clone()]
+        );
+
+        assert_ne!(mapped.id, root_id.tree_id);
+        assert_eq!(mapped.nodes.len(), 4);
+        assert_eq!(mapped.free_ids.len(), 1);
+        assert_eq!(mapped.free_ids[0].tree_id, mapped.id);
+
+        let new_root_id = mapped.translate_node_id(&root_id).unwrap();
+        let new_node_2_id = mapped.translate_node_id(&node_2_id).unwrap();
+        let new_node_4_id = mapped.translate_node_id(&node_4_id).unwrap();
+        assert_eq!(mapped.root_node_id(), Some(&new_root_id));
+        assert_eq!(mapped.get(&new_node_4_id).unwrap().data(), &40);
+        assert_eq!(
+            mapped.get(&new_node_4_id).unwrap().parent(),
+            Some(&new_node_2_id)
+        );
+        assert_eq!(
+            mapped.get(&new_root_id).unwrap().children(),
+            &vec![new_node_2_id]
+        );
+
+        assert_eq!(
+            mapped.translate_node_id(&node_3_id),
+            Err(NodeIdError::NodeIdNoLongerValid)
+        );
+
+        let mut other = Tree::new();
+        for data in 0..5 {
+            other.insert(Node::new(data), AsNewRoot).unwrap();
+        }
+        let out_of_range_id = other.insert(Node::new(5), AsNewRoot).unwrap();
+        assert_eq!(
+            mapped.translate_node_id(&out_of_range_id),
+            Err(NodeIdError::InvalidNodeIdForTree)
+        );
     }
 
     #[test]
-    fn test_remove_node_orphan_children() {
+    fn test_map_ref() {
         use InsertBehavior::*;
         use RemoveBehavior::*;
 
-        let mut tree = TreeBuilder::new().with_root(Node::new(5)).build();
-
-        let root_id = tree.root.clone().unwrap();
-
-        let node_1_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
-        let node_2_id = tree.insert(Node::new(2), UnderNode(&node_1_id)).unwrap();
-        let node_3_id = tree.insert(Node::new(3), UnderNode(&node_1_id)).unwrap();
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new("a"), AsRoot).unwrap();
+        let child_id = tree.insert(Node::new("bc"), UnderNode(&root_id)).unwrap();
 
-        let node_1 = tree.remove_node(node_1_id.clone(), OrphanChildren).unwrap();
+        let mut mapped = tree.map_ref(|node| node.data().len());
+        assert_eq!(tree.get(&child_id).unwrap().data(), &"bc");
 
-        assert_eq!(Some(&root_id), tree.root_node_id());
+        let new_child_id = mapped.translate_node_id(&child_id).unwrap();
+        assert_eq!(mapped.get(&new_child_id).unwrap().data(), &2);
 
-        assert_eq!(node_1.data(), &1);
-        assert_eq!(node_1.children().len(), 0);
-        assert!(node_1.parent().is_none());
-        assert!(tree.get(&node_1_id).is_err());
+        // the slot is reused after a removal, so the old id no longer translates
+        mapped.remove_node(new_child_id, DropChildren).unwrap();
+        let new_root_id = mapped.translate_node_id(&root_id).unwrap();
+        mapped
+            .insert(Node::new(3), UnderNode(&new_root_id))
+            .unwrap();
+        assert_eq!(
+            mapped.translate_node_id(&child_id),
+            Err(NodeIdError::NodeIdNoLongerValid)
+        );
+    }
 
-        let node_2_ref = tree.get(&node_2_id).unwrap();
-        let node_3_ref = tree.get(&node_3_id).unwrap();
+    #[test]
+    fn test_fold_subtree() {
+        use InsertBehavior::*;
 
-        assert_eq!(node_2_ref.data(), &2);
-        assert_eq!(node_3_ref.data(), &3);
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+        let node_2_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(3), UnderNode(&node_2_id)).unwrap();
+        tree.insert(Node::new(4), UnderNode(&node_2_id)).unwrap();
+        tree.insert(Node::new(5), UnderNode(&root_id)).unwrap();
+
+        let rendered = tree
+            .fold_subtree(&root_id, |node, children: Vec<String>| {
+                if children.is_empty() {
+                    node.data().to_string()
+                } else {
+                    format!("{}({})", node.data(), children.join(" "))
+                }
+            })
+            .unwrap();
+        assert_eq!(rendered, "1(2(3 4) 5)");
 
-        assert!(node_2_ref.parent().is_none());
-        assert!(node_3_ref.parent().is_none());
+        let sum = tree
+            .fold_subtree(&node_2_id, |node, children: Vec<i32>| {
+                node.data() + children.iter().sum::<i32>()
+            })
+            .unwrap();
+        assert_eq!(sum, 9);
     }
 
     #[test]
-    fn test_remove_root() {
-        use RemoveBehavior::*;
+    // quadratic when every insertion checks the whole Tree
+    #[cfg(not(all(feature = "integrity_checks", debug_assertions)))]
+    fn test_fold_subtree_deep() {
+        use InsertBehavior::*;
 
-        let mut tree = TreeBuilder::new().with_root(Node::new(5)).build();
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let mut parent_id = root_id.clone();
+        for i in 1..100_000 {
+            parent_id = tree.insert(Node::new(i), UnderNode(&parent_id)).unwrap();
+        }
 
-        let root_id = tree.root.clone().unwrap();
-        tree.remove_node(root_id.clone(), OrphanChildren).unwrap();
-        assert_eq!(None, tree.root_node_id());
+        let depth = tree
+            .fold_subtree(&root_id, |_, children: Vec<usize>| {
+                1 + children.into_iter().max().unwrap_or(0)
+            })
+            .unwrap();
+        assert_eq!(depth, 100_000);
+    }
 
-        let mut tree = TreeBuilder::new().with_root(Node::new(5)).build();
+    #[test]
+    // quadratic when every insertion checks the whole Tree
+    #[cfg(not(all(feature = "integrity_checks", debug_assertions)))]
+    fn test_fold_subtree_wide() {
+        use InsertBehavior::*;
 
-        let root_id = tree.root.clone().unwrap();
-        tree.remove_node(root_id.clone(), LiftChildren).unwrap();
-        assert_eq!(None, tree.root_node_id());
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        for i in 1..100_000 {
+            tree.insert(Node::new(i), UnderNode(&root_id)).unwrap();
+        }
+
+        let (count, sum) = tree
+            .fold_subtree(&root_id, |node, children: Vec<(usize, u64)>| {
+                children
+                    .into_iter()
+                    .fold((1, *node.data() as u64), |(count, sum), child| {
+                        (count + child.0, sum + child.1)
+                    })
+            })
+            .unwrap();
+        assert_eq!(count, 100_000);
+        assert_eq!(sum, 99_999 * 100_000 / 2);
     }
 
     #[test]
@@ -2840,6 +5739,7 @@ mod tree_tests {
             assert_eq!(tree.get(&node_3_id).unwrap().parent(), Some(&node_6_id));
             assert_eq!(tree.get(&node_4_id).unwrap().parent(), Some(&node_6_id));
             assert_eq!(tree.get(&node_6_id).unwrap().parent(), Some(&node_1_id));
+            assert!(tree.get(&node_3_id).unwrap().children().is_empty());
 
             assert!(tree
                 .get(&node_1_id,)
@@ -3062,4 +5962,694 @@ mod tree_tests {
         // ensure the tree and the cloned tree are equal
         assert_eq!(tree, cloned);
     }
+
+    type Snapshot = (
+        Option<NodeId>,
+        Vec<Option<(i32, Option<NodeId>, Vec<NodeId>)>>,
+        Vec<usize>,
+        Vec<NodeId>,
+    );
+
+    fn snapshot(tree: &Tree<i32>) -> Snapshot {
+        let nodes = tree
+            .nodes
+            .iter()
+            .map(|node| {
+                node.as_ref().map(|node| {
+                    (
+                        *node.data(),
+                        node.parent().cloned(),
+                        node.children().clone(),
+                    )
+                })
+            })
+            .collect();
+
+        (
+            tree.root.clone(),
+            nodes,
+            tree.generations.clone(),
+            tree.free_ids.clone(),
+        )
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        use InsertBehavior::*;
+        use MoveBehavior::*;
+        use RemoveBehavior::*;
+        use SwapBehavior::*;
+
+        let mut tree = Tree::new();
+        tree.enable_journal();
+
+        let mut snapshots = vec![snapshot(&tree)];
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        snapshots.push(snapshot(&tree));
+
+        let mut ids = vec![root_id.clone()];
+        for i in 1..10 {
+            let parent_id = ids[(i as usize - 1) / 3].clone();
+            ids.push(tree.insert(Node::new(i), UnderNode(&parent_id)).unwrap());
+            snapshots.push(snapshot(&tree));
+        }
+
+        tree.remove_node(ids[1].clone(), DropChildren).unwrap();
+        snapshots.push(snapshot(&tree));
+        let id_10 = tree.insert(Node::new(10), UnderNodeAt(&ids[2], 0)).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.remove_node(ids[2].clone(), LiftChildren).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.remove_node(ids[3].clone(), OrphanChildren).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.insert(Node::new(11), AsRoot).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.move_node(&ids[9], ToParent(&ids[7])).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.move_node(&ids[7], ToRoot).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.swap_nodes(&ids[0], &ids[7], TakeChildren).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.swap_nodes(&ids[8], &id_10, LeaveChildren).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.swap_nodes(&ids[0], &ids[8], ChildrenOnly).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.sort_children_by(&ids[7], |a, b| b.data().cmp(a.data()))
+            .unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.sort_children_by_data(&ids[7]).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.sort_children_by_key(&ids[7], |node| -node.data())
+            .unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.make_nth_sibling(&id_10, 1).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.make_first_sibling(&id_10).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.make_last_sibling(&id_10).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.remove_subtree(ids[9].clone()).unwrap();
+        snapshots.push(snapshot(&tree));
+        tree.duplicate_subtree(&ids[0], UnderNode(&ids[8])).unwrap();
+        snapshots.push(snapshot(&tree));
+
+        // every call is exactly one step
+        for expected in snapshots.iter().rev().skip(1) {
+            assert!(tree.undo());
+            assert_eq!(&snapshot(&tree), expected);
+        }
+        assert!(!tree.undo());
+
+        for expected in snapshots.iter().skip(1) {
+            assert!(tree.redo());
+            assert_eq!(&snapshot(&tree), expected);
+        }
+        assert!(!tree.redo());
+    }
+
+    #[test]
+    fn test_undo_keeps_data_changes() {
+        use InsertBehavior::*;
+        use MoveBehavior::*;
+        use RemoveBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        tree.enable_journal();
+
+        let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        *tree.get_mut(&root_id).unwrap().data_mut() = 10;
+        tree.undo();
+        assert_eq!(tree.get(&root_id).unwrap().data(), &10);
+
+        // a removed node comes back with the data it had when it was removed
+        tree.redo();
+        *tree.get_mut(&child_id).unwrap().data_mut() = 11;
+        tree.remove_node(child_id.clone(), DropChildren).unwrap();
+        tree.undo();
+        assert_eq!(tree.get(&child_id).unwrap().data(), &11);
+
+        // recording a new change drops the redo history
+        tree.undo();
+        assert!(tree.can_redo());
+        tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        assert!(!tree.can_redo());
+
+        // failed calls are not recorded
+        let other_id = Tree::new().insert(Node::new(0), AsRoot).unwrap();
+        assert!(tree.move_node(&other_id, ToRoot).is_err());
+        tree.undo();
+        assert!(!tree.can_undo());
+
+        tree.disable_journal();
+        tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+        assert!(!tree.can_undo());
+        assert!(!tree.undo());
+    }
+
+    #[test]
+    fn test_undo_does_not_reuse_node_ids() {
+        use InsertBehavior::*;
+        use RemoveBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        tree.enable_journal();
+
+        // an insertion into a new slot
+        let a = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        tree.undo();
+        tree.redo();
+        assert_eq!(tree.get(&a).unwrap().data(), &1);
+        tree.undo();
+
+        let b = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        assert_ne!(a, b);
+        assert!(tree.get(&a).is_err());
+        assert_eq!(tree.get(&b).unwrap().data(), &2);
+
+        // an insertion into a freed slot
+        tree.remove_node(b.clone(), DropChildren).unwrap();
+        let c = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+        tree.undo();
+        tree.undo();
+        tree.remove_node(b.clone(), DropChildren).unwrap();
+
+        let d = tree.insert(Node::new(4), UnderNode(&root_id)).unwrap();
+        assert_ne!(c, d);
+        assert!(tree.get(&b).is_err());
+        assert!(tree.get(&c).is_err());
+        assert_eq!(tree.get(&d).unwrap().data(), &4);
+    }
+
+    #[test]
+    fn test_events() {
+        use super::super::TreeEvent::*;
+        use InsertBehavior::*;
+        use MoveBehavior::*;
+        use RemoveBehavior::*;
+        use SwapBehavior::*;
+
+        let mut tree = Tree::new();
+        let a = tree.insert(Node::new("a"), AsRoot).unwrap();
+        tree.enable_events();
+
+        let b = tree.insert(Node::new("b"), AsRoot).unwrap();
+        let c = tree.insert(Node::new("c"), UnderNode(&a)).unwrap();
+        let d = tree.insert(Node::new("d"), UnderNodeAt(&a, 0)).unwrap();
+        let e = tree.insert(Node::new("e"), AfterSibling(&d)).unwrap();
+        assert_eq!(
+            tree.drain_events(),
+            vec![
+                Inserted {
+                    id: b.clone(),
+                    parent: None,
+                    index: 0,
+                },
+                Moved {
+                    id: a.clone(),
+                    old_parent: None,
+                    new_parent: Some(b.clone()),
+                },
+                Inserted {
+                    id: c.clone(),
+                    parent: Some(a.clone()),
+                    index: 0,
+                },
+                Inserted {
+                    id: d.clone(),
+                    parent: Some(a.clone()),
+                    index: 0,
+                },
+                Inserted {
+                    id: e.clone(),
+                    parent: Some(a.clone()),
+                    index: 1,
+                },
+            ]
+        );
+
+        // b -> a -> [d, e, c]
+        tree.move_node(&a, ToParent(&c)).unwrap();
+        tree.move_node(&e, ToRoot).unwrap();
+        assert_eq!(
+            tree.drain_events(),
+            vec![
+                Moved {
+                    id: c.clone(),
+                    old_parent: Some(a.clone()),
+                    new_parent: Some(b.clone()),
+                },
+                Moved {
+                    id: a.clone(),
+                    old_parent: Some(b.clone()),
+                    new_parent: Some(c.clone()),
+                },
+                Moved {
+                    id: e.clone(),
+                    old_parent: Some(a.clone()),
+                    new_parent: None,
+                },
+                Moved {
+                    id: b.clone(),
+                    old_parent: None,
+                    new_parent: Some(e.clone()),
+                },
+            ]
+        );
+
+        // e -> b -> c -> a -> d
+        let f = tree.insert(Node::new("f"), UnderNode(&a)).unwrap();
+        tree.drain_events();
+        tree.swap_nodes(&d, &f, TakeChildren).unwrap();
+        tree.sort_children_by_data(&a).unwrap();
+        tree.make_last_sibling(&d).unwrap();
+        assert!(!tree.make_last_sibling(&d).unwrap());
+        tree.make_nth_sibling(&d, 0).unwrap();
+        assert_eq!(
+            tree.drain_events(),
+            vec![
+                Swapped {
+                    first: d.clone(),
+                    second: f.clone(),
+                    behavior: TakeChildren,
+                },
+                ChildrenReordered { parent: a.clone() },
+                ChildrenReordered { parent: a.clone() },
+                ChildrenReordered { parent: a.clone() },
+            ]
+        );
+
+        let mut other = Tree::new();
+        let g = other.insert(Node::new("g"), AsRoot).unwrap();
+        let h = other.insert(Node::new("h"), UnderNode(&g)).unwrap();
+        let id_map = tree.graft(other, UnderNode(&d)).unwrap();
+        tree.remove_node(c.clone(), LiftChildren).unwrap();
+        tree.replace_data(&d, "D").unwrap();
+        assert!(tree.remove_node(c.clone(), DropChildren).is_err());
+        assert_eq!(
+            tree.drain_events(),
+            vec![
+                Inserted {
+                    id: id_map[&g].clone(),
+                    parent: Some(d.clone()),
+                    index: 0,
+                },
+                Inserted {
+                    id: id_map[&h].clone(),
+                    parent: Some(id_map[&g].clone()),
+                    index: 0,
+                },
+                Removed {
+                    id: c.clone(),
+                    behavior: LiftChildren,
+                },
+                DataReplaced { id: d.clone() },
+            ]
+        );
+
+        tree.disable_events();
+        tree.remove_subtree(a).unwrap();
+        assert!(tree.drain_events().is_empty());
+    }
+
+    #[test]
+    fn test_events_with_undo() {
+        use super::super::TreeEvent::*;
+        use InsertBehavior::*;
+        use RemoveBehavior::*;
+
+        let mut tree = Tree::new();
+        tree.enable_journal();
+        tree.enable_events();
+
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        tree.remove_node(child_id.clone(), DropChildren).unwrap();
+        assert_eq!(tree.drain_events().len(), 3);
+
+        assert!(tree.undo());
+        assert_eq!(tree.drain_events(), vec![Reset]);
+        assert!(tree.get(&child_id).is_ok());
+
+        assert!(tree.redo());
+        assert!(!tree.redo());
+        assert_eq!(tree.drain_events(), vec![Reset]);
+
+        // only the undos that changed something are reported
+        while tree.undo() {}
+        assert!(!tree.undo());
+        assert_eq!(tree.drain_events(), vec![Reset, Reset, Reset]);
+        assert_eq!(tree.root_node_id(), None);
+    }
+
+    #[test]
+    fn test_write_dot_attribute_names() {
+        use super::super::DotOptions;
+        use InsertBehavior::*;
+
+        let mut tree = Tree::new();
+        tree.insert(Node::new(0), AsRoot).unwrap();
+
+        let options = DotOptions::new().with_attributes(|_: &i32| {
+            vec![
+                ("fill_color2".to_string(), "red".to_string()),
+                ("x] ; n1 [y".to_string(), "1".to_string()),
+                ("2d".to_string(), "2".to_string()),
+                ("a\"b".to_string(), "3".to_string()),
+                (String::new(), "4".to_string()),
+            ]
+        });
+
+        let mut s = String::new();
+        tree.write_dot(&mut s, &options).unwrap();
+        assert_eq!(
+            &s,
+            "digraph {\n    n0 [label=\"0\", fill_color2=\"red\", \"x] ; n1 [y\"=\"1\", \
+             \"2d\"=\"2\", \"a\\\"b\"=\"3\", \"\"=\"4\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_write_dot_with_orphans() {
+        use super::super::DotOptions;
+        use InsertBehavior::*;
+        use RemoveBehavior::*;
+
+        let mut s = String::new();
+        Tree::<i32>::new()
+            .write_dot(&mut s, &DotOptions::new())
+            .unwrap();
+        assert_eq!(&s, "digraph {\n}\n");
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let child_id = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        tree.insert(Node::new(2), UnderNode(&child_id)).unwrap();
+        tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+        tree.remove_node(child_id, OrphanChildren).unwrap();
+
+        let options = DotOptions::from_label_fn(|data: &i32| format!("<{}>", data));
+
+        let mut s = String::new();
+        tree.write_dot(&mut s, &options).unwrap();
+        assert_eq!(
+            &s,
+            "digraph {\n    n0 [label=\"<0>\"];\n    n3 [label=\"<3>\"];\n    n0 -> n3;\n}\n"
+        );
+
+        let options = options.with_orphans(true);
+
+        let mut s = String::new();
+        tree.write_dot(&mut s, &options).unwrap();
+        assert_eq!(
+            &s,
+            "digraph {\n    n0 [label=\"<0>\"];\n    n3 [label=\"<3>\"];\n    \
+             n2 [label=\"<2>\"];\n    n0 -> n3;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_check_integrity() {
+        use InsertBehavior::*;
+        use IntegrityError;
+        use MoveBehavior::*;
+        use RemoveBehavior::*;
+        use SwapBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let a = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let b = tree.insert(Node::new(2), UnderNode(&a)).unwrap();
+        let c = tree.insert(Node::new(3), UnderNode(&b)).unwrap();
+        let d = tree.insert(Node::new(4), UnderNode(&root_id)).unwrap();
+        assert_eq!(tree.check_integrity(), Ok(()));
+
+        tree.swap_nodes(&a, &c, TakeChildren).unwrap();
+        assert_eq!(tree.check_integrity(), Ok(()));
+        tree.swap_nodes(&b, &d, LeaveChildren).unwrap();
+        assert_eq!(tree.check_integrity(), Ok(()));
+        tree.swap_nodes(&root_id, &b, ChildrenOnly).unwrap();
+        assert_eq!(tree.check_integrity(), Ok(()));
+        tree.move_node(&c, ToParent(&d)).unwrap();
+        assert_eq!(tree.check_integrity(), Ok(()));
+        tree.move_node(&d, ToRoot).unwrap();
+        assert_eq!(tree.check_integrity(), Ok(()));
+        tree.remove_node(a.clone(), LiftChildren).unwrap();
+        assert_eq!(tree.check_integrity(), Ok(()));
+
+        let mut broken = tree.clone();
+        broken.free_ids.clear();
+        assert_eq!(
+            broken.check_integrity(),
+            Err(IntegrityError::FreeIdsMismatch(a.index))
+        );
+
+        let mut broken = tree.clone();
+        let root_id = broken.root.clone().unwrap();
+        let child_id = broken.get(&root_id).unwrap().children()[0].clone();
+        broken.get_mut_unsafe(&child_id).parent = None;
+        assert_eq!(
+            broken.check_integrity(),
+            Err(IntegrityError::ParentMismatch {
+                parent: root_id,
+                child: child_id,
+            })
+        );
+    }
+
+    #[test]
+    fn test_orphans() {
+        use InsertBehavior::*;
+        use RemoveBehavior::*;
+        use TreeEvent;
+
+        let mut tree = Tree::new();
+        assert_eq!(tree.forest_roots().next(), None);
+        assert_eq!(tree.drop_orphans(), 0);
+
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let a = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let b = tree.insert(Node::new(2), UnderNode(&a)).unwrap();
+        let c = tree.insert(Node::new(3), UnderNode(&a)).unwrap();
+        let d = tree.insert(Node::new(4), UnderNode(&c)).unwrap();
+        let e = tree.insert(Node::new(5), UnderNode(&root_id)).unwrap();
+
+        tree.remove_node(a, OrphanChildren).unwrap();
+        assert_eq!(
+            tree.orphan_ids().collect::<Vec<_>>(),
+            vec![b.clone(), c.clone()]
+        );
+        assert_eq!(
+            tree.forest_roots().collect::<Vec<_>>(),
+            vec![root_id.clone(), b.clone(), c.clone()]
+        );
+        assert_eq!(tree.reachable_count(), 2);
+        assert_eq!(tree.orphaned_count(), 3);
+
+        // re-using a free slot doesn't bring back an orphan
+        tree.remove_node(b.clone(), DropChildren).unwrap();
+        let f = tree.insert(Node::new(6), UnderNode(&e)).unwrap();
+        assert_eq!(tree.orphan_ids().collect::<Vec<_>>(), vec![c.clone()]);
+
+        tree.enable_events();
+        assert_eq!(tree.drop_orphans(), 2);
+        assert_eq!(
+            tree.drain_events(),
+            vec![TreeEvent::Removed {
+                id: c.clone(),
+                behavior: DropChildren,
+            }]
+        );
+        assert!(tree.get(&c).is_err());
+        assert!(tree.get(&d).is_err());
+        assert_eq!(tree.reachable_count(), 3);
+        assert_eq!(tree.orphaned_count(), 0);
+        assert_eq!(tree.check_integrity(), Ok(()));
+
+        // without a root `Node`, every subtree is orphaned
+        tree.remove_node(root_id, LiftChildren).unwrap();
+        assert_eq!(tree.orphan_ids().collect::<Vec<_>>(), vec![e.clone()]);
+        assert_eq!(tree.reachable_count(), 0);
+        assert_eq!(tree.orphaned_count(), 2);
+        assert_eq!(tree.drop_orphans(), 2);
+        assert!(tree.get(&f).is_err());
+        assert_eq!(tree.forest_roots().next(), None);
+    }
+
+    #[test]
+    // quadratic when every insertion checks the whole Tree
+    #[cfg(not(all(feature = "integrity_checks", debug_assertions)))]
+    fn test_sizes_of_deep_tree() {
+        use InsertBehavior::*;
+        use RemoveBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let mut node_id = root_id.clone();
+        let mut middle_id = root_id.clone();
+        for i in 1..100_000 {
+            node_id = tree.insert(Node::new(i), UnderNode(&node_id)).unwrap();
+            if i == 50_000 {
+                middle_id = node_id.clone();
+            }
+        }
+        tree.insert(Node::new(-1), UnderNode(&root_id)).unwrap();
+
+        assert_eq!(tree.len(), 100_001);
+        assert_eq!(tree.height(), 100_000);
+        assert_eq!(tree.depth(&node_id), Ok(99_999));
+        assert_eq!(tree.subtree_height(&middle_id), Ok(50_000));
+        assert_eq!(tree.subtree_size(&root_id), Ok(100_001));
+        assert_eq!(tree.subtree_size(&middle_id), Ok(50_000));
+
+        tree.remove_node(middle_id.clone(), OrphanChildren).unwrap();
+        assert_eq!(tree.len(), 100_000);
+        assert_eq!(tree.reachable_count(), 50_001);
+        assert_eq!(tree.orphaned_count(), 49_999);
+        assert_eq!(
+            tree.subtree_size(&middle_id),
+            Err(NodeIdError::NodeIdNoLongerValid)
+        );
+    }
+
+    #[test]
+    fn test_lowest_common_ancestor_and_path() {
+        use InsertBehavior::*;
+        use RemoveBehavior::*;
+
+        //       0
+        //      / \
+        //     1   5
+        //    / \
+        //   2   3
+        //       |
+        //       4
+        let mut tree = Tree::new();
+        let n0 = tree.insert(Node::new(0), AsRoot).unwrap();
+        let n1 = tree.insert(Node::new(1), UnderNode(&n0)).unwrap();
+        let n2 = tree.insert(Node::new(2), UnderNode(&n1)).unwrap();
+        let n3 = tree.insert(Node::new(3), UnderNode(&n1)).unwrap();
+        let n4 = tree.insert(Node::new(4), UnderNode(&n3)).unwrap();
+        let n5 = tree.insert(Node::new(5), UnderNode(&n0)).unwrap();
+
+        assert_eq!(tree.is_ancestor_of(&n1, &n4), Ok(true));
+        assert_eq!(tree.is_ancestor_of(&n0, &n4), Ok(true));
+        assert_eq!(tree.is_ancestor_of(&n2, &n4), Ok(false));
+        assert_eq!(tree.is_ancestor_of(&n4, &n1), Ok(false));
+        assert_eq!(tree.is_ancestor_of(&n4, &n4), Ok(false));
+
+        assert_eq!(tree.lowest_common_ancestor(&n2, &n4), Ok(Some(n1.clone())));
+        assert_eq!(tree.lowest_common_ancestor(&n4, &n2), Ok(Some(n1.clone())));
+        assert_eq!(tree.lowest_common_ancestor(&n4, &n5), Ok(Some(n0.clone())));
+        assert_eq!(tree.lowest_common_ancestor(&n3, &n4), Ok(Some(n3.clone())));
+        assert_eq!(tree.lowest_common_ancestor(&n4, &n4), Ok(Some(n4.clone())));
+
+        let path = |from: &NodeId, to: &NodeId| -> Vec<i32> {
+            tree.path(from, to)
+                .unwrap()
+                .unwrap()
+                .map(|id| *tree.get(&id).unwrap().data())
+                .collect()
+        };
+        assert_eq!(path(&n4, &n2), vec![4, 3, 1, 2]);
+        assert_eq!(path(&n2, &n5), vec![2, 1, 0, 5]);
+        assert_eq!(path(&n0, &n4), vec![0, 1, 3, 4]);
+        assert_eq!(path(&n4, &n0), vec![4, 3, 1, 0]);
+        assert_eq!(path(&n3, &n3), vec![3]);
+
+        let mut other_tree = Tree::new();
+        let other_id = other_tree.insert(Node::new(0), AsRoot).unwrap();
+        assert_eq!(
+            tree.lowest_common_ancestor(&n1, &other_id),
+            Err(NodeIdError::InvalidNodeIdForTree)
+        );
+        assert_eq!(
+            tree.is_ancestor_of(&other_id, &n1),
+            Err(NodeIdError::InvalidNodeIdForTree)
+        );
+
+        // nodes in different subtrees have no common ancestor
+        tree.remove_node(n1.clone(), OrphanChildren).unwrap();
+        assert_eq!(tree.lowest_common_ancestor(&n2, &n4), Ok(None));
+        assert_eq!(tree.lowest_common_ancestor(&n4, &n5), Ok(None));
+        assert!(tree.path(&n2, &n4).unwrap().is_none());
+        assert_eq!(tree.is_ancestor_of(&n0, &n4), Ok(false));
+        assert_eq!(
+            tree.path(&n1, &n4).err(),
+            Some(NodeIdError::NodeIdNoLongerValid)
+        );
+        assert_eq!(
+            tree.is_ancestor_of(&n0, &n1),
+            Err(NodeIdError::NodeIdNoLongerValid)
+        );
+    }
+
+    #[test]
+    fn test_sibling_navigation() {
+        use InsertBehavior::*;
+        use RemoveBehavior::*;
+
+        let mut tree = Tree::new();
+        let root_id = tree.insert(Node::new(0), AsRoot).unwrap();
+        let a = tree.insert(Node::new(1), UnderNode(&root_id)).unwrap();
+        let b = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+        let c = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+        let d = tree.insert(Node::new(4), UnderNode(&b)).unwrap();
+
+        assert_eq!(tree.index_in_parent(&a), Ok(0));
+        assert_eq!(tree.index_in_parent(&c), Ok(2));
+        assert_eq!(tree.index_in_parent(&d), Ok(0));
+        assert_eq!(tree.next_sibling_id(&b), Ok(Some(&c)));
+        assert_eq!(tree.prev_sibling_id(&b), Ok(Some(&a)));
+        assert_eq!(tree.next_sibling_id(&d), Ok(None));
+        assert_eq!(tree.next_sibling(&a).unwrap().unwrap().data(), &2);
+        assert!(tree.prev_sibling(&a).unwrap().is_none());
+
+        // the root `Node` has no siblings
+        assert_eq!(
+            tree.index_in_parent(&root_id),
+            Err(NodeIdError::NodeHasNoParent)
+        );
+        assert_eq!(tree.next_sibling_id(&root_id), Ok(None));
+        assert_eq!(tree.prev_sibling_id(&root_id), Ok(None));
+
+        tree.make_first_sibling(&c).unwrap();
+        assert_eq!(tree.index_in_parent(&c), Ok(0));
+        assert_eq!(
+            tree.following_sibling_ids(&c).unwrap().collect::<Vec<_>>(),
+            vec![&a, &b]
+        );
+
+        tree.make_nth_sibling(&c, 1).unwrap();
+        assert_eq!(
+            tree.sibling_ids(&c).unwrap().collect::<Vec<_>>(),
+            vec![&a, &b]
+        );
+        assert_eq!(tree.index_in_parent(&c), Ok(1));
+
+        // orphaned `Node`s have no siblings either
+        tree.remove_node(b.clone(), OrphanChildren).unwrap();
+        assert_eq!(tree.index_in_parent(&d), Err(NodeIdError::NodeHasNoParent));
+        assert_eq!(tree.siblings(&d).unwrap().count(), 0);
+        assert_eq!(tree.next_sibling_id(&a), Ok(Some(&c)));
+
+        assert_eq!(
+            tree.next_sibling(&b).err(),
+            Some(NodeIdError::NodeIdNoLongerValid)
+        );
+        assert_eq!(
+            tree.preceding_sibling_ids(&b).err(),
+            Some(NodeIdError::NodeIdNoLongerValid)
+        );
+        let other_tree: Tree<i32> = Tree::new();
+        assert_eq!(
+            other_tree.index_in_parent(&a),
+            Err(NodeIdError::InvalidNodeIdForTree)
+        );
+        assert_eq!(
+            other_tree.following_siblings(&a).err(),
+            Some(NodeIdError::InvalidNodeIdForTree)
+        );
+    }
 }
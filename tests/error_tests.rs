@@ -1,13 +1,16 @@
 extern crate id_tree;
 
+use id_tree::FormatError;
 use id_tree::InsertBehavior::*;
 use id_tree::MoveBehavior::*;
 use id_tree::Node;
 use id_tree::NodeIdError::*;
+use id_tree::PersistentTree;
 use id_tree::RemoveBehavior::*;
 use id_tree::SwapBehavior::*;
 use id_tree::Tree;
 use id_tree::TreeBuilder;
+use id_tree::TreeFormatter;
 
 #[test]
 fn test_old_node_id() {
@@ -28,6 +31,30 @@ fn test_old_node_id() {
     assert_eq!(error, NodeIdNoLongerValid);
 }
 
+#[test]
+fn test_old_node_id_after_slot_reuse() {
+    let mut tree: Tree<i32> = TreeBuilder::new().build();
+
+    let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+    let child_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    let child_id_copy = child_id.clone();
+
+    tree.remove_node(child_id, DropChildren).unwrap();
+
+    // this re-uses the slot that was freed above
+    let new_child_id = tree.insert(Node::new(3), UnderNode(&root_id)).unwrap();
+
+    let result = tree.get(&child_id_copy);
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap(), NodeIdNoLongerValid);
+
+    let result = tree.remove_node(child_id_copy, DropChildren);
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap(), NodeIdNoLongerValid);
+
+    assert_eq!(tree.get(&new_child_id).unwrap().data(), &3);
+}
+
 #[test]
 fn test_get_node_from_other_tree() {
     let mut tree_a: Tree<i32> = TreeBuilder::new().build();
@@ -60,6 +87,53 @@ fn test_get_mut_node_from_other_tree() {
     assert_eq!(root_node_b.err().unwrap(), InvalidNodeIdForTree);
 }
 
+#[test]
+fn test_insert_at_child_index_out_of_bounds() {
+    let mut tree: Tree<i32> = TreeBuilder::new().build();
+
+    let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+    tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+
+    let result = tree.insert(Node::new(3), UnderNodeAt(&root_id, 2));
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap(), InvalidChildIndex);
+}
+
+#[test]
+fn test_insert_next_to_root() {
+    let mut tree: Tree<i32> = TreeBuilder::new().build();
+
+    let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+
+    let result = tree.insert(Node::new(2), BeforeSibling(&root_id));
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap(), NodeHasNoParent);
+
+    let result = tree.insert(Node::new(2), AfterSibling(&root_id));
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap(), NodeHasNoParent);
+}
+
+#[test]
+fn test_insert_next_to_sibling_from_other_tree() {
+    let mut tree_a: Tree<i32> = TreeBuilder::new().build();
+    let mut tree_b: Tree<i32> = TreeBuilder::new().build();
+
+    let root_id_a = tree_a.insert(Node::new(1), AsRoot).unwrap();
+    let child_id_a = tree_a.insert(Node::new(2), UnderNode(&root_id_a)).unwrap();
+    tree_b.insert(Node::new(1), AsRoot).unwrap();
+
+    // note use of wrong tree
+    let result = tree_b.insert(Node::new(3), UnderNodeAt(&root_id_a, 0));
+    assert_eq!(result.err().unwrap(), InvalidNodeIdForTree);
+
+    let result = tree_b.insert(Node::new(3), BeforeSibling(&child_id_a));
+    assert_eq!(result.err().unwrap(), InvalidNodeIdForTree);
+
+    let result = tree_b.insert(Node::new(3), AfterSibling(&child_id_a));
+    assert_eq!(result.err().unwrap(), InvalidNodeIdForTree);
+}
+
 #[test]
 fn test_remove_node_lift_children_from_other_tree() {
     let mut tree_a: Tree<i32> = TreeBuilder::new().build();
@@ -105,6 +179,116 @@ fn test_remove_node_remove_children_from_other_tree() {
     assert_eq!(error, InvalidNodeIdForTree);
 }
 
+#[test]
+fn test_remove_subtree_from_other_tree() {
+    let mut tree_a: Tree<i32> = TreeBuilder::new().build();
+    let mut tree_b: Tree<i32> = TreeBuilder::new().build();
+
+    let root_node_id_a = tree_a.insert(Node::new(1), AsRoot).unwrap();
+
+    // note use of wrong tree
+    let result = tree_b.remove_subtree(root_node_id_a);
+    assert!(result.is_err());
+
+    let error = result.err().unwrap();
+    assert_eq!(error, InvalidNodeIdForTree);
+}
+
+#[test]
+fn test_graft_under_node_from_other_tree() {
+    let mut tree_a: Tree<i32> = TreeBuilder::new().build();
+    let mut tree_b: Tree<i32> = TreeBuilder::new().build();
+    let mut other: Tree<i32> = TreeBuilder::new().build();
+
+    let root_node_id_a = tree_a.insert(Node::new(1), AsRoot).unwrap();
+    tree_b.insert(Node::new(1), AsRoot).unwrap();
+    other.insert(Node::new(2), AsRoot).unwrap();
+
+    // note use of wrong tree
+    let result = tree_b.graft(other, UnderNode(&root_node_id_a));
+    assert!(result.is_err());
+
+    let error = result.err().unwrap();
+    assert_eq!(*error.error(), InvalidNodeIdForTree);
+}
+
+#[test]
+fn test_graft_under_node_old_id() {
+    let mut tree: Tree<i32> = TreeBuilder::new().build();
+    let mut other: Tree<i32> = TreeBuilder::new().build();
+
+    let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+    let child_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    other.insert(Node::new(3), AsRoot).unwrap();
+    // `.clone()` required to get this error
+    let child_id_clone = child_id.clone();
+    tree.remove_node(child_id, DropChildren).unwrap();
+
+    // note usage of cloned `NodeId`
+    let result = tree.graft(other, UnderNode(&child_id_clone));
+    assert!(result.is_err());
+
+    let error = result.err().unwrap();
+    assert_eq!(*error.error(), NodeIdNoLongerValid);
+
+    // nothing has been taken from the other tree
+    let other = error.into_tree();
+    assert_eq!(other.len(), 1);
+    assert_eq!(other.get(other.root_node_id().unwrap()).unwrap().data(), &3);
+    assert_eq!(tree.len(), 1);
+}
+
+#[test]
+fn test_copy_subtree_from_other_tree() {
+    let tree_a: Tree<i32> = TreeBuilder::new().build();
+    let mut tree_b: Tree<i32> = TreeBuilder::new().build();
+
+    let root_node_id_b = tree_b.insert(Node::new(1), AsRoot).unwrap();
+
+    // note use of wrong tree
+    let result = tree_a.copy_subtree(&root_node_id_b, &mut tree_b, AsRoot);
+    assert!(result.is_err());
+
+    let error = result.err().unwrap();
+    assert_eq!(error, InvalidNodeIdForTree);
+}
+
+#[test]
+fn test_duplicate_subtree_old_id() {
+    let mut tree: Tree<i32> = TreeBuilder::new().build();
+
+    let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+    let child_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    // `.clone()` required to get this error
+    let child_id_clone = child_id.clone();
+    tree.remove_node(child_id, DropChildren).unwrap();
+
+    // note usage of cloned `NodeId`
+    let result = tree.duplicate_subtree(&child_id_clone, UnderNode(&root_id));
+    assert!(result.is_err());
+
+    let error = result.err().unwrap();
+    assert_eq!(error, NodeIdNoLongerValid);
+}
+
+#[test]
+fn test_remove_subtree_old_id() {
+    let mut tree: Tree<i32> = TreeBuilder::new().build();
+
+    let root_id = tree.insert(Node::new(1), AsRoot).unwrap();
+    let child_id = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    // `.clone()` required to get this error
+    let child_id_clone = child_id.clone();
+    tree.remove_subtree(child_id).unwrap();
+
+    // note usage of cloned `NodeId`
+    let result = tree.remove_subtree(child_id_clone);
+    assert!(result.is_err());
+
+    let error = result.err().unwrap();
+    assert_eq!(error, NodeIdNoLongerValid);
+}
+
 #[test]
 fn test_move_node_into_other_tree() {
     let mut tree_a: Tree<i32> = TreeBuilder::new().build();
@@ -361,3 +545,111 @@ fn test_children_ids_old_id() {
     let error = ancestors.err().unwrap();
     assert_eq!(error, NodeIdNoLongerValid);
 }
+
+#[test]
+fn test_fold_subtree_different_trees() {
+    let mut a = Tree::new();
+    let b = Tree::<i32>::new();
+
+    let root_id = a.insert(Node::new(1), AsRoot).unwrap();
+
+    // note usage of `b` instead of `a`
+    let result = b.fold_subtree(&root_id, |_, _: Vec<()>| ());
+
+    assert!(result.is_err());
+    let error = result.err().unwrap();
+    assert_eq!(error, InvalidNodeIdForTree);
+}
+
+#[test]
+fn test_fold_subtree_old_id() {
+    let mut a = Tree::new();
+
+    let root_id = a.insert(Node::new(1), AsRoot).unwrap();
+    // `.clone()` required to get this error
+    let root_id_clone = root_id.clone();
+    let _ = a.remove_node(root_id, DropChildren).unwrap();
+
+    // note usage of cloned `NodeId`
+    let result = a.fold_subtree(&root_id_clone, |_, _: Vec<()>| ());
+
+    assert!(result.is_err());
+    let error = result.err().unwrap();
+    assert_eq!(error, NodeIdNoLongerValid);
+}
+
+#[test]
+fn test_replace_data_old_id() {
+    let mut a = Tree::new();
+
+    let root_id = a.insert(Node::new(1), AsRoot).unwrap();
+    // `.clone()` required to get this error
+    let root_id_clone = root_id.clone();
+    let _ = a.remove_node(root_id, DropChildren).unwrap();
+
+    // note usage of cloned `NodeId`
+    let result = a.replace_data(&root_id_clone, 2);
+
+    assert!(result.is_err());
+    let error = result.err().unwrap();
+    assert_eq!(error, NodeIdNoLongerValid);
+}
+
+#[test]
+fn test_tree_formatter_start_from_other_tree() {
+    let mut a = Tree::new();
+    let b = Tree::<i32>::new();
+
+    let root_id = a.insert(Node::new(1), AsRoot).unwrap();
+
+    // note usage of `b` instead of `a`
+    let result = TreeFormatter::new()
+        .with_start(&root_id)
+        .write(&b, &mut String::new());
+
+    assert!(result.is_err());
+    let error = result.err().unwrap();
+    assert_eq!(error, FormatError::NodeIdError(InvalidNodeIdForTree));
+}
+
+#[test]
+fn test_persistent_insert_under_node_from_other_tree() {
+    let (_, root_id_a) = PersistentTree::new().insert(Node::new(1), AsRoot).unwrap();
+    let tree_b: PersistentTree<i32> = PersistentTree::new();
+
+    // note use of wrong tree
+    let result = tree_b.insert(Node::new(2), UnderNode(&root_id_a));
+    assert!(result.is_err());
+
+    let error = result.err().unwrap();
+    assert_eq!(error, InvalidNodeIdForTree);
+}
+
+#[test]
+fn test_persistent_old_node_id() {
+    let (tree, root_id) = PersistentTree::new().insert(Node::new(1), AsRoot).unwrap();
+    let (tree, child_id) = tree.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+    let removed = tree.remove_node(&child_id, DropChildren).unwrap();
+
+    let result = removed.move_node(&child_id, ToRoot);
+    assert!(result.is_err());
+
+    let error = result.err().unwrap();
+    assert_eq!(error, NodeIdNoLongerValid);
+
+    // still valid for the version that contains it
+    assert!(tree.move_node(&child_id, ToRoot).is_ok());
+}
+
+#[test]
+fn test_persistent_node_id_from_newer_version() {
+    let (old, root_id) = PersistentTree::new().insert(Node::new(1), AsRoot).unwrap();
+    let (_, child_id) = old.insert(Node::new(2), UnderNode(&root_id)).unwrap();
+
+    // note use of a version that predates the `Node`
+    let result = old.swap_nodes(&root_id, &child_id, TakeChildren);
+    assert!(result.is_err());
+
+    let error = result.err().unwrap();
+    assert_eq!(error, NodeIdNoLongerValid);
+}
@@ -6,6 +6,7 @@ use id_tree::{Node, Tree, TreeBuilder};
 type NodeData = i32;
 
 #[test]
+#[allow(clippy::useless_conversion)]
 fn post_order_traversal_ids() {
     let mut tree: Tree<NodeData> = TreeBuilder::new().build();
 